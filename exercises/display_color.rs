@@ -0,0 +1,33 @@
+// Exercise: implement Display for Color (from Example 1: Hello World, Formatting Traits)
+//
+// Each color should print as its red, green and blue values in decimal, then the same values as
+// a hex code written twice, e.g.
+//     RGB (128, 255, 90) 0x80FF5A #80FF5A
+// Hex codes always use two upper-case digits per value.
+//
+// Check your answer with `cargo run -- exercise check 1/display_color <this file>`
+use std::fmt::{self, Display, Formatter};
+
+struct Color {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        todo!("write the color to f")
+    }
+}
+
+fn main() {
+    for color in [
+        Color { red: 128, green: 255, blue: 90 },
+        Color { red: 0, green: 3, blue: 254 },
+        Color { red: 0, green: 0, blue: 0 },
+    ]
+    .iter()
+    {
+        println!("{}", color);
+    }
+}
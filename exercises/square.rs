@@ -0,0 +1,25 @@
+// Exercise: make a square Rectangle (from Example 3: Custom Types, C Structs)
+//
+// `square` takes the bottom left corner of a square and the length of its sides, and returns
+// the Rectangle covering it: p1 is the bottom left corner and p2 the top right.
+//
+// Check your answer with `cargo run -- exercise check 3/square <this file>`
+#[derive(Debug)]
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Debug)]
+struct Rectangle {
+    p1: Point,
+    p2: Point,
+}
+
+fn square(lower_left: Point, size: f32) -> Rectangle {
+    todo!("build the Rectangle")
+}
+
+fn main() {
+    println!("{:?}", square(Point { x: 10.5, y: 10.1 }, 3.1415926));
+}
@@ -0,0 +1,31 @@
+// Exercise: transpose a Matrix (from Example 2: Primitives, Tuples)
+//
+// Transposing swaps the rows and the columns, so
+//     ( 1.1 1.2 )        ( 1.1 2.1 )
+//     ( 2.1 2.2 )  ->    ( 1.2 2.2 )
+//
+// Check your answer with `cargo run -- exercise check 2/transpose <this file>`
+use std::fmt;
+
+#[derive(Debug)]
+struct Matrix(f32, f32, f32, f32);
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\t( {:.1} {:.1} )\n\t( {:.1} {:.1} )",
+            self.0, self.1, self.2, self.3
+        )
+    }
+}
+
+fn transpose(matrix: Matrix) -> Matrix {
+    todo!("swap the rows and columns of matrix")
+}
+
+fn main() {
+    let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
+    println!("Matrix:\n{}", matrix);
+    println!("Transpose:\n{}", transpose(matrix));
+}
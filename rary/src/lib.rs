@@ -0,0 +1,13 @@
+// The library hands back what it has to say rather than printing it,
+// so the binary can render it like the rest of its output
+pub fn public_function() -> String {
+    "called library's `public_function()`".to_string()
+}
+
+fn private_function() -> String {
+    "called library's `private_function()`".to_string()
+}
+
+pub fn indirect_access() -> String {
+    format!("called library's `indirect_access()`, that\n> {}", private_function())
+}
@@ -1,156 +1,147 @@
-use super::helpers;
+use super::chapter::{question, Chapter, CompileFail, Exercise, Question, Section};
+use super::diagnostics;
+use super::render::{narrate, output, output_inline, show_and_run};
 use std::fmt::{self, Display, Formatter};
 
+const SOURCE: &str = include_str!("a01_hello_world.rs");
+
 fn macros() {
-    helpers::section_title(format!("Macros"));
-    println!("Print with a series of macros in std::fmt, including:");
-    println!("format! writes formatted text to String");
-    print!("print! does the same as format! but also prints to stdout\n");
-    eprint!("eprint! does the same as format! but also prints to stderr\n");
-    println!("println! does the same as print! but appends a newline (\\n)");
-    eprintln!("eprintln! does the same as eprint! but appends a newline (\\n)");
+    narrate!("Print with a series of macros in std::fmt, including:");
+    narrate!("format! writes formatted text to String");
+    narrate!("print! does the same as format! but also prints to stdout");
+    narrate!("eprint! does the same as format! but also prints to stderr");
+    narrate!("println! does the same as print! but appends a newline (\\n)");
+    narrate!("eprintln! does the same as eprint! but appends a newline (\\n)");
 }
 
+#[allow(clippy::approx_constant, clippy::excessive_precision, clippy::print_literal)]
 fn formatting() {
-    helpers::section_title(format!("Formatting:"));
-    println!("Use {{}} to include variables");
-    println!("{{}} days in December -> {} days in December", 31);
-    eprintln!(" (When the arguments are ..., 31)");
-
-    println!("There are a lot of formatting patterns than can be used");
-
-    println!("\n\t1. You can number the arguments positionally like {{0}} and {{1}}:");
-    print!("{{0}}, this is {{1}}. {{1}}, meet {{0}}. Have fun! -> ");
-    println!("{0}, this is {1}. {1}, meet {0}. Have fun!", "Alice", "Bob");
-    eprintln!(" (When the arguments are ..., \"Alice\", \"Bob\")");
-
-    println!("\n\t2. You can name arguments like {{subject}} and {{index}}:");
-    print!("{{subject}} {{verb}} {{object}} -> ");
-    println!(
-        "{subject} {verb} {object}",
-        object = "the lazy dog",
-        subject = "the quick brown fox",
-        verb = "jumps over"
-    );
-    eprintln!(" (When the arguments are ...,");
-    eprintln!("\t\tobject = \"the lazy dog\",");
-    eprintln!("\t\tsubject = \"the quick brown fox\",");
-    eprintln!("\t\tverb = \"jumps over\"");
-    eprintln!(" )");
-
-    println!("\n\t3. Special formatting can be specified after a :, like {{:b}}");
-    println!("\tSpecifically:");
-    println!("\t\tnone - Display");
-    println!("\t\t? - Debug");
-    println!("\t\tx? - Debug with lowercase hexidecimal integers");
-    println!("\t\tX? - Debug with uppercase hexidecimal integers");
-    println!("\t\to - octal");
-    println!("\t\tx - LowerHex");
-    println!("\t\tX - UpperHex");
-    println!("\t\tp - Pointer");
-    println!("\t\tb - Binary");
-    println!("\t\te - LowerExp");
-    println!("\t\tE - UpperExp");
-
-    println!("\n\t3a. Number formats can be specified, like {{:b}} for binary:");
-    print!("{{}} of {{:b}} people know binary, the other half doesn't -> ");
-    println!(
-        "{} of {:b} people know binary, the other half doesn't",
-        1, 2
-    );
-    eprintln!(" (When the arguments are ..., 1, 2)");
-    print!("{{}} of {{:o}} people know octal, the other 90% don't -> ");
-    println!("{} of {:o} people know octal, the other 90% don't", 1, 10);
-    eprintln!(" (When the arguments are ..., 1, 10)");
-    print!("{{}} of {{:x}} people know hex, the other 99.96% don't -> ");
-    println!(
-        "{} of {:x} people know hex, the other 99.96% don't",
-        1, 3199
-    );
-    eprintln!(" (When the arguments are ..., 1, 3199)");
-    print!("{{}} of {{:X}} people know hex, the other 96.77% don't -> ");
-    println!("{} of {:X} people know hex, the other 96.77% don't", 1, 31);
-    eprintln!(" (When the arguments are ..., 1, 31)");
-    print!("{{:?}} -> ");
-    println!("{:?}", (1, 2, 3, 4));
-    eprintln!(" (When the arguments are ..., (1, 2, 3, 4))");
-
-    println!("\n\t3b. Align text/numbers with < ^ >, like {{value:>width$}}");
-    print!("{{value:<width$}} -> ");
-    println!("\"{value:<width$}\"", value = 31, width = 10);
-    eprintln!(" (When the arguments are ...,");
-    eprintln!("\t\tvalue = 31,");
-    eprintln!("\t\twidth = 10,");
-    eprintln!(" )");
-    print!("{{0:^1$}} -> ");
-    println!("\"{0:^1$}\"", 31, 10);
-    eprintln!(" (When the arguments are ..., 31, 10)");
-    print!("{{0:>10}} -> ");
-    println!("\"{0:>10}\"", 31);
-    eprintln!(" (When the arguments are ..., 31)");
-
-    println!("\n\t3c. You can pad numbers with zeroes, or anything with anything, really");
-    print!("{{0:0>1$}} -> ");
-    println!("\"{0:0>1$}\"", 31, 10);
-    eprintln!(" (When the arguments are ..., 31)");
-    print!("{{0:*^7}} -> ");
-    println!("\"{0:*^7}\"", "fig");
-    eprintln!(" (When the arguments are ..., \"fig\")");
-    print!("{{0:-<9}} -> ");
-    println!("\"{0:-<9}\"", "fig");
-    eprintln!(" (When the arguments are ..., \"fig\")");
-
-    println!("\n\t4. Rust will ensure the correct number of arguments are needed.");
-    print!("println!(\"My name is {{0}}, {{1}} {{0}}\", \"Bond\"); -> ");
-    println!("error: invalid reference to positional argument 1 (there is 1 argument)");
-
-    println!("\n\t5. Custom types like structs won't print. See Debug for how to print them.");
-    // struct Structure(i32);
-    // println!("This struct '{}' won't print...", Structure(3));
-
-    println!("\n\t6. You can truncate decimals");
-    let pi = 3.1415926;
-    print!("Pi is roughly {{:.*}} -> ");
-    println!("Pi is roughly {:.*}", 3, pi);
-    eprintln!(" (When the arguments are ..., 3, {pi})", pi = pi);
+    narrate!("Use {{}} to include variables");
+    show_and_run! { SOURCE;
+        output!("{} days in December", 31);
+    }
+
+    narrate!("There are a lot of formatting patterns than can be used");
+
+    narrate!("\n\t1. You can number the arguments positionally like {{0}} and {{1}}:");
+    show_and_run! { SOURCE;
+        output!("{0}, this is {1}. {1}, meet {0}. Have fun!", "Alice", "Bob");
+    }
+
+    narrate!("\n\t2. You can name arguments like {{subject}} and {{index}}:");
+    show_and_run! { SOURCE;
+        output!(
+            "{subject} {verb} {object}",
+            object = "the lazy dog",
+            subject = "the quick brown fox",
+            verb = "jumps over"
+        );
+    }
+
+    narrate!("\n\t3. Special formatting can be specified after a :, like {{:b}}");
+    narrate!("\tSpecifically:");
+    narrate!("\t\tnone - Display");
+    narrate!("\t\t? - Debug");
+    narrate!("\t\tx? - Debug with lowercase hexidecimal integers");
+    narrate!("\t\tX? - Debug with uppercase hexidecimal integers");
+    narrate!("\t\to - octal");
+    narrate!("\t\tx - LowerHex");
+    narrate!("\t\tX - UpperHex");
+    narrate!("\t\tp - Pointer");
+    narrate!("\t\tb - Binary");
+    narrate!("\t\te - LowerExp");
+    narrate!("\t\tE - UpperExp");
+
+    narrate!("\n\t3a. Number formats can be specified, like {{:b}} for binary:");
+    show_and_run! { SOURCE;
+        output!("{} of {:b} people know binary, the other half doesn't", 1, 2);
+    }
+    show_and_run! { SOURCE;
+        output!("{} of {:o} people know octal, the other 90% don't", 1, 10);
+    }
+    show_and_run! { SOURCE;
+        output!("{} of {:x} people know hex, the other 99.96% don't", 1, 3199);
+    }
+    show_and_run! { SOURCE;
+        output!("{} of {:X} people know hex, the other 96.77% don't", 1, 31);
+    }
+    show_and_run! { SOURCE;
+        output!("{:?}", (1, 2, 3, 4));
+    }
+
+    narrate!("\n\t3b. Align text/numbers with < ^ >, like {{value:>width$}}");
+    show_and_run! { SOURCE;
+        output!("\"{value:<width$}\"", value = 31, width = 10);
+    }
+    show_and_run! { SOURCE;
+        output!("\"{0:^1$}\"", 31, 10);
+    }
+    show_and_run! { SOURCE;
+        output!("\"{0:>10}\"", 31);
+    }
+
+    narrate!("\n\t3c. You can pad numbers with zeroes, or anything with anything, really");
+    show_and_run! { SOURCE;
+        output!("\"{0:0>1$}\"", 31, 10);
+    }
+    show_and_run! { SOURCE;
+        output!("\"{0:*^7}\"", "fig");
+    }
+    show_and_run! { SOURCE;
+        output!("\"{0:-<9}\"", "fig");
+    }
+
+    narrate!("\n\t4. Rust will ensure the correct number of arguments are needed.");
+    output_inline!("println!(\"My name is {{0}}, {{1}} {{0}}\", \"Bond\"); -> ");
+    narrate!("error: invalid reference to positional argument 1 (there is 1 argument)");
+
+    narrate!("\n\t5. Custom types like structs won't print. See Debug for how to print them.");
+    diagnostics::show(&NOT_DISPLAY);
+
+    narrate!("\n\t6. You can truncate decimals");
+    show_and_run! { SOURCE;
+        let pi = 3.1415926;
+        output!("Pi is roughly {:.*}", 3, pi);
+    }
 }
 
 fn debug() {
-    helpers::section_title(format!("Debug"));
-    println!("To use the std::fmt traits they need to be implemented on the type");
-    println!("Automatic impelementations are provided for types from std, but others must be manually implemented.");
-    println!("\nThe fmt::Debug trait can derive a fmt::Debug implementation for all types:");
+    narrate!("To use the std::fmt traits they need to be implemented on the type");
+    narrate!("Automatic impelementations are provided for types from std, but others must be manually implemented.");
+    narrate!("\nThe fmt::Debug trait can derive a fmt::Debug implementation for all types:");
 
+    #[allow(dead_code)]
     #[derive(Debug)]
     struct Structure(i32);
 
+    #[allow(dead_code)]
     #[derive(Debug)]
     struct Deep(Structure);
 
-    println!("Now {:?} will print", Structure(3));
-    println!("Now {:?} will print", Deep(Structure(7)));
+    output!("Now {:?} will print", Structure(3));
+    output!("Now {:?} will print", Deep(Structure(7)));
 
-    println!("\nYou can use Debug {{:?}} formatting to print any of the std types too (I don't show this here)");
-    println!("\nRust can also pretty-print with the built in formatter {{:#?}}");
+    narrate!("\nYou can use Debug {{:?}} formatting to print any of the std types too (I don't show this here)");
+    narrate!("\nRust can also pretty-print with the built in formatter {{:#?}}");
 
+    #[allow(dead_code)]
     #[derive(Debug)]
     struct Person<'a> {
         name: &'a str,
         age: u8,
-    };
+    }
 
     let name = "Connor";
     let age = 32;
     let person = Person { name, age };
 
-    println!("{:#?}", person);
+    output!("{:#?}", person);
 
-    println!("You can manually implement fmt::Display if you need to.");
+    narrate!("You can manually implement fmt::Display if you need to.");
 }
 
 fn display() {
-    helpers::section_title(format!("Display"));
-    println!("fmt::Debug looks ugly");
+    narrate!("fmt::Debug looks ugly");
 
     #[derive(Debug)]
     struct MinMax(i64, i64);
@@ -202,32 +193,31 @@ fn display() {
         imag: 7.2,
     };
 
-    println!("Compare Structures:");
-    println!("Display: {}", minmax);
-    println!("Debug: {:?}", minmax);
+    narrate!("Compare Structures:");
+    output!("Display: {}", minmax);
+    output!("Debug: {:?}", minmax);
 
-    println!(
+    output!(
         "Display: The big range is {big} and the small is {small}",
         small = small_range,
         big = big_range
     );
-    println!(
+    output!(
         "Debug: The big range is {:?} and the small is {:?}",
         big_range, small_range,
     );
 
-    println!("Compare points:");
-    println!("Display: {}", point);
-    println!("Debug: {:?}", point);
+    narrate!("Compare points:");
+    output!("Display: {}", point);
+    output!("Debug: {:?}", point);
 
-    println!("Compare complex number:");
-    println!("Display: {}", complex_number);
-    println!("Debug: {:?}", complex_number);
+    narrate!("Compare complex number:");
+    output!("Display: {}", complex_number);
+    output!("Debug: {:?}", complex_number);
 }
 
 fn list() {
-    helpers::section_title(format!("Testcase: List"));
-    println!("Each write! generates a fmt::Result, but they can be chained together with a ?");
+    narrate!("Each write! generates a fmt::Result, but they can be chained together with a ?");
 
     struct List(Vec<i32>);
     impl fmt::Display for List {
@@ -245,17 +235,46 @@ fn list() {
     }
 
     let v = List(vec![1, 2, 3]);
-    println!("{}", v);
+    output!("{}", v);
 }
 
+// Color lives outside formatting_traits so the `display_color` exercise can check answers against it
+struct Color {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let hex_red = format!("{:X}", self.red);
+        let hex_green = format!("{:X}", self.green);
+        let hex_blue = format!("{:X}", self.blue);
+        let hex_rgb = format!("{0:0>2}{1:0>2}{2:0>2}", hex_red, hex_green, hex_blue);
+        write!(
+            f,
+            "RGB ({red}, {green}, {blue}) 0x{hex_rgb} #{hex_rgb}",
+            red=self.red, green=self.green, blue=self.blue, hex_rgb=hex_rgb
+        )
+    }
+}
+
+const COLORS: [Color; 3] = [
+    Color {red: 128, green: 255, blue: 90},
+    Color {red: 0, green: 3, blue: 254},
+    Color {red: 0, green: 0, blue: 0},
+];
+
+#[allow(clippy::disallowed_names, clippy::excessive_precision)]
 fn formatting_traits() {
-    helpers::section_title(format!("Formatting Traits"));
-    println!("The same value can be formatted different ways depending on which argument is used");
+    narrate!("The same value can be formatted different ways depending on which argument is used");
 
-    let foo: i64 = 3735928559;
-    println!("{{}}, foo -> {}", foo);
-    println!("0x{{:X}}, foo -> 0x{:X}", foo);
-    println!("0o{{:o}}, foo -> 0o{:o}", foo);
+    show_and_run! { SOURCE;
+        let foo: i64 = 3735928559;
+        output!("{}", foo);
+        output!("0x{:X}", foo);
+        output!("0o{:o}", foo);
+    }
 
     struct City {
         name: &'static str,
@@ -280,7 +299,7 @@ fn formatting_traits() {
         }
     }
 
-    println!("\nCities:");
+    narrate!("\nCities:");
     for city in [
         City {
             name: "Dublin",
@@ -300,58 +319,106 @@ fn formatting_traits() {
     ]
     .iter()
     {
-        println!("\t- {}", city);
+        output!("\t- {}", city);
     }
 
-    struct Color {
-        red: u8,
-        green: u8,
-        blue: u8,
-    }
-
-    impl Display for Color {
-        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-            let hex_red = format!("{:X}", self.red);
-            let hex_green = format!("{:X}", self.green);
-            let hex_blue = format!("{:X}", self.blue);
-            let hex_rgb = format!("{0:0>2}{1:0>2}{2:0>2}", hex_red, hex_green, hex_blue);
-            write!(
-                f,
-                "RGB ({red}, {green}, {blue}) 0x{hex_rgb} #{hex_rgb}",
-                red=self.red, green=self.green, blue=self.blue, hex_rgb=hex_rgb
-            )
-        }
-    }
-
-    println!("\nColors:");
-    for color  in [
-        Color {red: 128, green: 255, blue: 90},
-        Color {red: 0, green: 3, blue: 254},
-        Color {red: 0, green: 0, blue: 0},
-    ].iter() {
-        println!("{}", color);
+    narrate!("\nColors:");
+    for color in COLORS.iter() {
+        output!("{}", color);
     }
 }
 
-pub fn run() {
+fn intro() {
     // This is an exmaple of a single-line comment
     // Each line comment starts with //
-    helpers::example_title(format!("Example {}: {}", 1, "Hello World"));
-    println!("Hello World!");
-    println!("I'm a Rustacean!");
+    narrate!("Hello World!");
+    narrate!("I'm a Rustacean!");
     /*
     * This is a block comment, by convention/for style the lines for a block
     * comment start with a *
     (but it's not actually necessary)
     */
-    // Notice how block comments can be used within an expression
-    let x = 5 + /* 90 + */ 5;
-    println!("\nIs x 100 or 10? x = {}", x);
-
-    macros();
-    formatting();
-    debug();
-    display();
-    list();
-    formatting_traits();
+    show_and_run! { SOURCE;
+        // Notice how block comments can be used within an expression
+        let x = 5 + /* 90 + */ 5;
+        output!("Is x 100 or 10? x = {}", x);
+    }
+}
+
+#[allow(clippy::approx_constant)]
+fn quiz() -> Vec<Question> {
+    vec![
+        question!("{0}, this is {1}. {1}, meet {0}. Have fun!", "Alice", "Bob"),
+        question!("{} of {:b} people know binary, the other half doesn't", 1, 2),
+        question!("{:o}", 10),
+        question!("{:X}", 3199),
+        question!("{:?}", (1, 2, 3, 4)),
+        question!("{0:0>1$}", 31, 10),
+        question!("{0:*^7}", "fig"),
+        question!("Pi is roughly {:.*}", 3, 3.1415926),
+    ]
+}
+
+fn display_color() -> String {
+    COLORS.iter().map(|color| format!("{}\n", color)).collect()
+}
+
+pub struct HelloWorld;
+
+const SECTIONS: &[Section] = &[
+    Section::new("macros", "Macros", macros),
+    Section::new("formatting", "Formatting", formatting),
+    Section::new("debug", "Debug", debug),
+    Section::new("display", "Display", display),
+    Section::new("list", "Testcase: List", list),
+    Section::new("formatting_traits", "Formatting Traits", formatting_traits),
+];
+
+const NOT_DISPLAY: CompileFail = CompileFail {
+    slug: "not_display",
+    context: "",
+    code: r#"
+struct Structure(i32);
+println!("This struct '{}' won't print...", Structure(3));
+"#,
+    error: "E0277",
+};
+
+const COMPILE_FAILS: &[CompileFail] = &[NOT_DISPLAY];
+
+const EXERCISES: &[Exercise] = &[Exercise {
+    slug: "display_color",
+    title: "Implement Display for Color",
+    stub: include_str!("../exercises/display_color.rs"),
+    expected: display_color,
+}];
+
+impl Chapter for HelloWorld {
+    fn slug(&self) -> &'static str {
+        "hello-world"
+    }
+
+    fn title(&self) -> &'static str {
+        "Hello World"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn quiz(&self) -> Vec<Question> {
+        quiz()
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_fails(&self) -> &'static [CompileFail] {
+        COMPILE_FAILS
+    }
 }
@@ -1,59 +1,61 @@
-use super::helpers;
+use super::chapter::{question, Chapter, Exercise, Question, Section};
+use super::panics;
+use super::render::{narrate, output, show_and_run};
 use std::fmt;
 use std::mem;
 use typename::TypeName;
 
-fn scalar_types() {
-    helpers::section_title(format!("Scalar Types"));
-
-    println!("i8   goes from {} to {}", std::i8::MIN, std::i8::MAX);
-    println!("i16  goes from {} to {}", std::i16::MIN, std::i16::MAX);
-    println!("i32  goes from {} to {}", std::i32::MIN, std::i32::MAX);
-    println!("i64  goes from {} to {}", std::i64::MIN, std::i64::MAX);
-    println!("i128 goes from {} to {}", std::i128::MIN, std::i128::MAX);
+const SOURCE: &str = include_str!("a02_primitives.rs");
 
-    println!("u8   goes from {} to {}", std::u8::MIN, std::u8::MAX);
-    println!("u16  goes from {} to {}", std::u16::MIN, std::u16::MAX);
-    println!("u32  goes from {} to {}", std::u32::MIN, std::u32::MAX);
-    println!("u64  goes from {} to {}", std::u64::MIN, std::u64::MAX);
-    println!("u128 goes from {} to {}", std::u128::MIN, std::u128::MAX);
-
-    println!(
+#[allow(clippy::print_literal)]
+fn scalar_types() {
+    output!("i8   goes from {} to {}", i8::MIN, i8::MAX);
+    output!("i16  goes from {} to {}", i16::MIN, i16::MAX);
+    output!("i32  goes from {} to {}", i32::MIN, i32::MAX);
+    output!("i64  goes from {} to {}", i64::MIN, i64::MAX);
+    output!("i128 goes from {} to {}", i128::MIN, i128::MAX);
+
+    output!("u8   goes from {} to {}", u8::MIN, u8::MAX);
+    output!("u16  goes from {} to {}", u16::MIN, u16::MAX);
+    output!("u32  goes from {} to {}", u32::MIN, u32::MAX);
+    output!("u64  goes from {} to {}", u64::MIN, u64::MAX);
+    output!("u128 goes from {} to {}", u128::MIN, u128::MAX);
+
+    output!(
         "f32  goes from {:.1} to {:.1}",
-        std::f32::MIN,
-        std::f32::MAX
+        f32::MIN,
+        f32::MAX
     );
-    println!(
+    output!(
         "f64  goes from {:.1} to {:.1}",
-        std::f64::MIN,
-        std::f64::MAX
+        f64::MIN,
+        f64::MAX
     );
 
-    println!("char represents scalar values at 4-bytes per 'a' 'α' or '∞'");
-    println!("single-quotes ('a') are chars, double quotes \"abc\" are strings");
-    println!("bool values are either true or false");
-    println!("\ttrue: {}", true);
-    println!("\tfalse: {}", false);
+    narrate!("char represents scalar values at 4-bytes per 'a' 'α' or '∞'");
+    narrate!("single-quotes ('a') are chars, double quotes \"abc\" are strings");
+    narrate!("bool values are either true or false");
+    output!("\ttrue: {}", true);
+    output!("\tfalse: {}", false);
 
-    println!(
+    output!(
         "And the unit type () which can only be an empty tuple:{:?}",
         ()
     );
 }
 
 fn compound_types() {
-    helpers::section_title(format!("Compound Types"));
-    println!("Arrays like {:?}", [1, 2, 3]);
-    println!("Tuples like {:?}", (1, true));
+    output!("Arrays like {:?}", [1, 2, 3]);
+    output!("Tuples like {:?}", (1, true));
 }
 
+#[allow(clippy::print_literal)]
 fn variable_annotation() {
-    helpers::section_title(format!("Variable Annotation"));
-    println!("Rust tries to infer the variable type from defaults or context");
-    println!("Integers default to i32, floats default to f64");
+    narrate!("Rust tries to infer the variable type from defaults or context");
+    narrate!("Integers default to i32, floats default to f64");
     let example_integer = 12;
     let example_float = 1.2;
-    println!(
+    output!(
         "integer: {}:{}, float: {}:{}",
         example_integer,
         example_integer.type_name_of(),
@@ -61,34 +63,34 @@ fn variable_annotation() {
         example_float.type_name_of()
     );
 
-    println!("\nYou can use 'regular' notation after the declared variable name:");
-    println!(
+    narrate!("\nYou can use 'regular' notation after the declared variable name:");
+    output!(
         "let logical:bool = true -> {}:{}",
         true,
         true.type_name_of()
     );
     let a_float: f32 = 235.25;
-    println!(
+    output!(
         "let a_float:f32 = 235.25 -> {}:{}",
         a_float,
         a_float.type_name_of()
     );
-    println!("You can use suffix annotation after the variable value:");
-    println!("let an_integer = 5u8 -> {}:{}", 5u8, 5u8.type_name_of());
-    println!("let an_integer = 5i64 -> {}:{}", 5i64, 5i64.type_name_of());
+    narrate!("You can use suffix annotation after the variable value:");
+    output!("let an_integer = 5u8 -> {}:{}", 5u8, 5u8.type_name_of());
+    output!("let an_integer = 5i64 -> {}:{}", 5i64, 5i64.type_name_of());
 
-    println!("\nA type can be inferred if not declared and they types basically match:");
+    narrate!("\nA type can be inferred if not declared and they types basically match:");
     let default = 12;
     let mut inferred = 12;
-    println!(
+    output!(
         "default = {}:{}, inferred = {}:{}",
         default,
         default.type_name_of(),
         inferred,
         inferred.type_name_of()
     );
-    inferred = std::i8::MIN;
-    println!(
+    inferred = i8::MIN;
+    output!(
         "because inferred is later set to {}:{}",
         inferred,
         inferred.type_name_of()
@@ -96,15 +98,15 @@ fn variable_annotation() {
 
     let default = 12;
     let mut inferred = 12;
-    println!(
+    output!(
         "default = {}:{}, inferred = {}:{}",
         default,
         default.type_name_of(),
         inferred,
         inferred.type_name_of()
     );
-    inferred = std::u8::MIN;
-    println!(
+    inferred = u8::MIN;
+    output!(
         "because inferred is later set to {}:{}",
         inferred,
         inferred.type_name_of()
@@ -112,98 +114,115 @@ fn variable_annotation() {
 
     let default = 1.2;
     let mut inferred = 1.2;
-    println!(
+    output!(
         "default = {}:{}, inferred = {}:{}",
         default,
         default.type_name_of(),
         inferred,
         inferred.type_name_of()
     );
-    inferred = std::f32::MIN;
-    println!(
+    inferred = f32::MIN;
+    output!(
         "because inferred is later set to {}:{}",
         inferred,
         inferred.type_name_of()
     );
 
-    println!("\nVariables that change must be declared as mutable with 'let mut'");
-    println!("Basically the same idea as let/const in JavaScript, just different expression");
-    println!("Unlike JavaScript, you can shadow non-mutables by redeclaring them i.e. let x = 1.2; let x = 12;");
-    println!("The previous value type isn't inferred from a shadowed declaration");
-    println!("So let mut x = 1; x = true; will error, but let x = 1; let x = true; will not")
+    narrate!("\nVariables that change must be declared as mutable with 'let mut'");
+    narrate!("Basically the same idea as let/const in JavaScript, just different expression");
+    narrate!("Unlike JavaScript, you can shadow non-mutables by redeclaring them i.e. let x = 1.2; let x = 12;");
+    narrate!("The previous value type isn't inferred from a shadowed declaration");
+    narrate!("So let mut x = 1; x = true; will error, but let x = 1; let x = true; will not")
 }
 
+#[allow(clippy::print_literal)]
 fn literals() {
-    helpers::section_title(format!("Literals"));
-    println!("Integers, floats, chars, strings, booleans, and the unit type can be expressed as literals");
-
-    println!("\tintegers: 12 -> {}", 12);
-    println!("\tfloats: 1.2 -> {}", 1.2);
-    println!("\tchars: 'a' -> {}", 'a');
-    println!("\tstrings: \"abc\" -> {}", "abc");
-    println!("\tbooleans: true -> {}", true);
-    println!("\tunit type: () -> {:?}", ());
-
-    println!("\nIntegers can also be expressed using base-specific notation:");
-    println!("\t0x12 -> {}", 0x12);
-    println!("\t0o12 -> {}", 0o12);
-    println!("\t0b11 -> {}", 0b11);
-
-    println!("\nUnderscores can be used to improve readability:");
-    println!("\t1_000 -> {}", 1_000);
-    println!("\t1_000_000 -> {}", 1_000_000);
-    println!("\t0.000_1 -> {}", 0.000_1);
-    println!("\t0.000_001 -> {}", 0.000_001);
+    narrate!("Integers, floats, chars, strings, booleans, and the unit type can be expressed as literals");
+
+    output!("\tintegers: 12 -> {}", 12);
+    output!("\tfloats: 1.2 -> {}", 1.2);
+    output!("\tchars: 'a' -> {}", 'a');
+    output!("\tstrings: \"abc\" -> {}", "abc");
+    output!("\tbooleans: true -> {}", true);
+    output!("\tunit type: () -> {:?}", ());
+
+    narrate!("\nIntegers can also be expressed using base-specific notation:");
+    output!("\t0x12 -> {}", 0x12);
+    output!("\t0o12 -> {}", 0o12);
+    output!("\t0b11 -> {}", 0b11);
+
+    narrate!("\nUnderscores can be used to improve readability:");
+    output!("\t1_000 -> {}", 1_000);
+    output!("\t1_000_000 -> {}", 1_000_000);
+    output!("\t0.000_1 -> {}", 0.000_1);
+    output!("\t0.000_001 -> {}", 0.000_001);
 }
 
+#[allow(clippy::nonminimal_bool)]
 fn operators() {
-    helpers::section_title(format!("Operators"));
-    println!("Operators and perator precedence in Rust is similar to other C-like languages");
-    println!("Unlike the other languages I'm used to types MUST match");
-    println!("\t+ addition: 1.1 + 2.0 = {}", 1.1 + 2.0);
-    println!("\t- subtraction: 1 - 2 = {}", 1 - 2);
-    println!("\t* multiplication: 14f32 * 2.5 = {}", 14f32 * 2.5);
-    println!("\t/ division: 5 / 2 = {}", 5 / 2);
-    println!("\t% modulo: 5f64 % 2.4 = {}", 5f64 % 2.4);
-
-    println!("\nBoolean operators:");
-    println!("\t&& AND: true && false = {}", true && false);
-    println!("\t|| OR: true || false = {}", true || false);
-    println!("\t! NOT: !true = {}", !true);
-
-    println!("\nBitwise operators:");
-    println!("\t& bitwise AND: 0011 & 0101 = {:04b}", 0b0011 & 0b0101);
-    println!("\t| bitwise OR: 0011 | 0101 = {:04b}", 0b0011 | 0b0101);
-    println!("\t^ bitwise XOR: 0011 ^ 0101 = {:04b}", 0b0011 ^ 0b0101);
-    println!("\t<< left-shift: 1 << 5 = {}", 1 << 5);
-    println!("\t>> right-shift: 0x80 >> 2 = 0x{:x}", 0x80 >> 2);
-
-    println!("\nThe usual comparison operators (==, !=, <, >, <=, >=)");
-    println!("And assignment operators (=, +=, -=, *=, /=, %=, &=, |=, ^=, <<=, >>=)");
+    narrate!("Operators and perator precedence in Rust is similar to other C-like languages");
+    narrate!("Unlike the other languages I'm used to types MUST match");
+    output!("\t+ addition: 1.1 + 2.0 = {}", 1.1 + 2.0);
+    output!("\t- subtraction: 1 - 2 = {}", 1 - 2);
+    output!("\t* multiplication: 14f32 * 2.5 = {}", 14f32 * 2.5);
+    output!("\t/ division: 5 / 2 = {}", 5 / 2);
+    output!("\t% modulo: 5f64 % 2.4 = {}", 5f64 % 2.4);
+
+    narrate!("\nBoolean operators:");
+    output!("\t&& AND: true && false = {}", true && false);
+    output!("\t|| OR: true || false = {}", true || false);
+    output!("\t! NOT: !true = {}", !true);
+
+    narrate!("\nBitwise operators:");
+    output!("\t& bitwise AND: 0011 & 0101 = {:04b}", 0b0011 & 0b0101);
+    output!("\t| bitwise OR: 0011 | 0101 = {:04b}", 0b0011 | 0b0101);
+    output!("\t^ bitwise XOR: 0011 ^ 0101 = {:04b}", 0b0011 ^ 0b0101);
+    output!("\t<< left-shift: 1 << 5 = {}", 1 << 5);
+    output!("\t>> right-shift: 0x80 >> 2 = 0x{:x}", 0x80 >> 2);
+
+    narrate!("\nThe usual comparison operators (==, !=, <, >, <=, >=)");
+    narrate!("And assignment operators (=, +=, -=, *=, /=, %=, &=, |=, ^=, <<=, >>=)");
+}
+
+// Matrix lives outside tuples so the `transpose` exercise can check answers against it
+#[derive(Debug)]
+struct Matrix(f32, f32, f32, f32);
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\t( {:.1} {:.1} )\n\t( {:.1} {:.1} )",
+            self.0, self.1, self.2, self.3
+        )
+    }
+}
+
+fn transpose(matrix: Matrix) -> Matrix {
+    Matrix(matrix.0, matrix.2, matrix.1, matrix.3)
 }
 
 fn tuples() {
-    helpers::section_title(format!("Tuples"));
-    println!("A tuple is a collection of values of differnt types.");
-    println!("Tuples are created using parentheses ()");
-    println!("Each tuple is a value with a type signature (T1, T2, ...) were T1, T2 are the types of its members");
-    println!("Function can use tuples to return multiple values");
+    narrate!("A tuple is a collection of values of differnt types.");
+    narrate!("Tuples are created using parentheses ()");
+    narrate!("Each tuple is a value with a type signature (T1, T2, ...) were T1, T2 are the types of its members");
+    narrate!("Function can use tuples to return multiple values");
 
     let long_tuple = (
         1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true,
     );
-    println!("\nValues can be extracted from tuples using tuple indexing:");
-    println!("\tif long_tuple = {:?};", long_tuple);
-    println!("\tlong_tuple.0; => {}", long_tuple.0);
-    println!("\tlong_tuple.6; => {}", long_tuple.6);
+    narrate!("\nValues can be extracted from tuples using tuple indexing:");
+    output!("\tif long_tuple = {:?};", long_tuple);
+    output!("\tlong_tuple.0; => {}", long_tuple.0);
+    output!("\tlong_tuple.6; => {}", long_tuple.6);
 
     let tuple_of_tuples = ((2i64, 3i8, -4i16, (-5f32, true, 'a')), (1i32, false));
-    println!("\nTuples can be tuple members, and can be printed with Debug {{:?}} if they have <= 12 members:");
-    println!("\t{:?}", tuple_of_tuples);
+    narrate!("\nTuples can be tuple members, and can be printed with Debug {{:?}} if they have <= 12 members:");
+    output!("\t{:?}", tuple_of_tuples);
 
-    println!("\nTo create a single-element tuple, you must include the comma");
-    println!("\tone element tuple: (5,) => {:?}", (5,));
-    println!("\tjust an integer: (5) => {:?}", (5));
+    narrate!("\nTo create a single-element tuple, you must include the comma");
+    output!("\tone element tuple: (5,) => {:?}", (5,));
+    output!("\tjust an integer: (5) => {:?}", (5));
 
     fn reverse(pair: (i32, bool)) -> (bool, i32) {
         // let can be used deconstructively on tuples
@@ -212,76 +231,118 @@ fn tuples() {
         (boolean, integer)
     }
     let pair = (25, true);
-    println!("\nlet can be used deconstructively on tuples like let (integer, boolean) = pair;");
-    println!("\tpair = {:?}; reverse(pair) => {:?}", pair, reverse(pair));
+    narrate!("\nlet can be used deconstructively on tuples like let (integer, boolean) = pair;");
+    output!("\tpair = {:?}; reverse(pair) => {:?}", pair, reverse(pair));
 
-    #[derive(Debug)]
-    struct Matrix(f32, f32, f32, f32);
     let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
-    println!("\nA struct that is a tuple can be treated like a tuple");
-    println!("\tmatrix => {:?}", matrix);
-    println!("\tmatrix.0 => {:?}", matrix.0);
-
-    impl fmt::Display for Matrix {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(
-                f,
-                "\t( {:.1} {:.1} )\n\t( {:.1} {:.1} )",
-                self.0, self.1, self.2, self.3
-            )
-        }
-    }
-    println!("But it's better to implement std::fmt::Display for better formatting");
-    println!("Matrix:\n{}", matrix);
-    fn transpose(matrix: Matrix) -> Matrix {
-        Matrix(matrix.0, matrix.2, matrix.1, matrix.3)
-    }
-    println!("Transpose:\n{}", transpose(matrix));
+    narrate!("\nA struct that is a tuple can be treated like a tuple");
+    output!("\tmatrix => {:?}", matrix);
+    output!("\tmatrix.0 => {:?}", matrix.0);
+
+    narrate!("But it's better to implement std::fmt::Display for better formatting");
+    output!("Matrix:\n{}", matrix);
+    output!("Transpose:\n{}", transpose(matrix));
 }
 
 fn arrays_and_slices () {
-    helpers::section_title(format!("Arrays and Slices"));
-    println!("Arrays are collections of objects of the same type, and are initialized with square brackets []");
-    println!("Their size, which is known at compile time, is part of their type signature [T; size]");
-
-    println!("\nSlices are similar to arrays, except their size is not known at compile time");
-    println!("A slice is a reference to a length of another piece of data");
-    println!("The first parameter is a pointer to the data, and the second parameter is the length of the slice");
-    println!("Slices can be used to borrow sections of an array and have the type signature &[T]");
+    narrate!("Arrays are collections of objects of the same type, and are initialized with square brackets []");
+    narrate!("Their size, which is known at compile time, is part of their type signature [T; size]");
+
+    narrate!("\nSlices are similar to arrays, except their size is not known at compile time");
+    narrate!("A slice is a reference to a length of another piece of data");
+    narrate!("The first parameter is a pointer to the data, and the second parameter is the length of the slice");
+    narrate!("Slices can be used to borrow sections of an array and have the type signature &[T]");
     
     let xs:[i32; 5] = [1, 2, 3, 4, 5]; // Fixed size array
-    println!("\nWith a fixed size array xs:[i32; 5] = [1, 2, 3, 4, 5]:");
-    println!("\tfirst element of the array: xs[0] => {}", xs[0]);
-    println!("\tsecond element of the array: xs[1] => {}", xs[1]);
-    println!("\tsize of the array: xs.len() => {}", xs.len());
-    println!("\tarray occupies {} bytes on the stack", mem::size_of_val(&xs));
+    narrate!("\nWith a fixed size array xs:[i32; 5] = [1, 2, 3, 4, 5]:");
+    output!("\tfirst element of the array: xs[0] => {}", xs[0]);
+    output!("\tsecond element of the array: xs[1] => {}", xs[1]);
+    output!("\tsize of the array: xs.len() => {}", xs.len());
+    output!("\tarray occupies {} bytes on the stack", mem::size_of_val(&xs));
     
     fn analyze_slice(slice: &[i32]){
-        println!("\tWith a borrowed slice slice:&[i32] = {:?}:", slice);
-        println!("\tfirst element of the slice: slice[0] => {}", slice[0]);
-        println!("\tsecond element of the slice: slice[1] => {}", slice[1]);
-        println!("\tsize of the slice: slice.len() => {}", slice.len());
-        println!("\tslice occupies {} bytes on the stack", mem::size_of_val(slice));
+        output!("\tWith a borrowed slice slice:&[i32] = {:?}:", slice);
+        output!("\tfirst element of the slice: slice[0] => {}", slice[0]);
+        output!("\tsecond element of the slice: slice[1] => {}", slice[1]);
+        output!("\tsize of the slice: slice.len() => {}", slice.len());
+        output!("\tslice occupies {} bytes on the stack", mem::size_of_val(slice));
     }
 
-    println!("\nYou can borrow a whole array as a slice:");
+    narrate!("\nYou can borrow a whole array as a slice:");
     analyze_slice(&xs);
 
     let ys:[i32; 500] = [0; 500]; // Elements initialized to the same value
-    println!("\nWith a array  of elements with the same initial value ys:[i32; 500] = [0; 500]:");
-    println!("\nYou can borrow a section of the array as a slice:");
+    narrate!("\nWith a array  of elements with the same initial value ys:[i32; 500] = [0; 500]:");
+    narrate!("\nYou can borrow a section of the array as a slice:");
     analyze_slice(&ys[35 .. 58]);
     
-    println!("\nUsing an out-of-bound index will cause a compile error");
+    narrate!("\nUsing an out-of-bound index on an array will cause a compile error");
+    narrate!("A slice's length isn't known until runtime though, so indexing past it panics:");
+    panics::show(|| {
+        show_and_run! { SOURCE;
+            let slice = &xs[..];
+            output!("{}", slice[5]);
+        }
+    });
 }
 
-pub fn run() {
-    helpers::example_title(format!("Example {}: {}", 2, "Primitives"));
-    scalar_types();
-    compound_types();
-    variable_annotation();
-    literals();
-    operators();
-    tuples();
-    arrays_and_slices();
+fn quiz() -> Vec<Question> {
+    vec![
+        question!("{}", i8::MIN),
+        question!("{}", u16::MAX),
+        question!("{}", 5 / 2),
+        question!("{:04b}", 0b0011 ^ 0b0101),
+        question!("{}", 1 << 5),
+        question!("0x{:x}", 0x80 >> 2),
+        question!("{:?}", (5,)),
+        question!("{}", std::mem::size_of_val(&[1i32, 2, 3, 4, 5])),
+        question!("{}", std::mem::size_of_val(&[0i32; 500][35..58])),
+    ]
+}
+
+fn transpose_matrix() -> String {
+    let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
+    let shown = format!("Matrix:\n{}\n", matrix);
+    format!("{}Transpose:\n{}\n", shown, transpose(matrix))
+}
+
+pub struct Primitives;
+
+const SECTIONS: &[Section] = &[
+    Section::new("scalar_types", "Scalar Types", scalar_types),
+    Section::new("compound_types", "Compound Types", compound_types),
+    Section::new("variable_annotation", "Variable Annotation", variable_annotation),
+    Section::new("literals", "Literals", literals),
+    Section::new("operators", "Operators", operators),
+    Section::new("tuples", "Tuples", tuples),
+    Section::new("arrays_and_slices", "Arrays and Slices", arrays_and_slices),
+];
+
+const EXERCISES: &[Exercise] = &[Exercise {
+    slug: "transpose",
+    title: "Transpose a Matrix",
+    stub: include_str!("../exercises/transpose.rs"),
+    expected: transpose_matrix,
+}];
+
+impl Chapter for Primitives {
+    fn slug(&self) -> &'static str {
+        "primitives"
+    }
+
+    fn title(&self) -> &'static str {
+        "Primitives"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn quiz(&self) -> Vec<Question> {
+        quiz()
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
 }
@@ -1,77 +1,83 @@
-use super::helpers;
+use super::chapter::{Chapter, Exercise, Section};
+use super::render::{listing, narrate, output, output_inline, show_and_run};
 
+const SOURCE: &str = include_str!("a03_custom_types.rs");
+
+// Point, Rectangle and square live outside c_structs so the `square` exercise can check answers against them
+#[derive(Debug)]
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Debug)]
+struct Rectangle {
+    p1: Point,
+    p2: Point,
+}
+
+fn square(lower_left: Point, size: f32) -> Rectangle {
+    let p1 = Point {
+        x: lower_left.x,
+        y: lower_left.y,
+    };
+    let p2 = Point {
+        x: lower_left.x + size,
+        y: lower_left.y + size,
+    };
+    Rectangle { p1, p2 }
+}
+
+#[allow(clippy::approx_constant)]
 fn c_structs() {
-    helpers::section_subtitle(format!("C Structs"));
-    println!("Classic C struct / JS/Python object (sort of) structure");
+    narrate!("Classic C struct / JS/Python object (sort of) structure");
+    #[allow(dead_code)]
     #[derive(Debug)]
     struct Person<'a> {
         name: &'a str,
         age: u8,
     }
-    #[derive(Debug)]
-    struct Point {
-        x: f32,
-        y: f32,
-    }
-    #[derive(Debug)]
-    struct Rectangle {
-        p1: Point,
-        p2: Point,
-    }
 
     let name = "Connor";
     let age = 32;
     let person = Person { name, age };
-    println!("\nWith: struct Person<'a> {{ name: &'a str, age: u8, }}");
-    println!("And name = {}; age = {};", name, age);
-    println!("\tPerson {{ name, age }} = {:?}", person);
-
-    let p1: Point = Point { x: 0.3, y: 0.4 };
-    let p2: Point = Point { x: 0.1, ..p1 };
-    println!("\nWith:");
-    println!("\tp1: Point = Point {{ x: 0.3, y: 0.4 }}");
-    println!("\tp2: Point = Point {{ x: 0.1, ..p1 }}");
-    println!("\n\tp1 coordinates x:{} y:{}", p1.x, p1.y);
-    println!("\tp2 coordinates x:{} y:{}", p2.x, p2.y);
-    println!("\tI used the struct update syntax to populate the fields of p2 from p1");
-
-    let Point { x: p1_x, y: p1_y } = p1;
-    let rectangle: Rectangle = Rectangle {
-        p1: Point {
-            x: p1_y,
-            y: p1_x + 1f32,
-        },
-        p2: p1,
-    };
-    println!("\nYou can destructure structs using a let binding:");
-    println!("\tlet Point {{ x: p1_x, y: p1_y }} = p1;");
-    println!("\tlet rectangle: Rectangle = Rectangle {{\n\t\tp1: Point {{ x: p1_y, y: p1_x + 1f32 }},\n\t\tp2: p1\n\t}};");
-    println!("\t=> {:?}", rectangle);
+    narrate!("\nWith: struct Person<'a> {{ name: &'a str, age: u8, }}");
+    output!("And name = {}; age = {};", name, age);
+    output!("\tPerson {{ name, age }} = {:?}", person);
+
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        let p1: Point = Point { x: 0.3, y: 0.4 };
+        let p2: Point = Point { x: 0.1, ..p1 };
+    }
+    output!("\n\tp1 coordinates x:{} y:{}", p1.x, p1.y);
+    output!("\tp2 coordinates x:{} y:{}", p2.x, p2.y);
+    narrate!("\tI used the struct update syntax to populate the fields of p2 from p1");
+
+    narrate!("\nYou can destructure structs using a let binding:");
+    show_and_run! { SOURCE;
+        let Point { x: p1_x, y: p1_y } = p1;
+        let rectangle: Rectangle = Rectangle {
+            p1: Point {
+                x: p1_y,
+                y: p1_x + 1f32,
+            },
+            p2: p1,
+        };
+    }
+    output!("\t=> {:?}", rectangle);
 
     fn rect_area(rect: Rectangle) {
-        println!("\nCalculating the area of a rectangle");
+        output!("\nCalculating the area of a rectangle");
         let length = (rect.p1.x - rect.p2.x).abs();
         let width = (rect.p1.y - rect.p2.y).abs();
-        println!("The rectangle decribed by {:?} has:", rect);
-        println!("\tLength: {} units", length);
-        println!("\tWidth: {} units", width);
-        println!("\tArea: {} square units", width * length);
+        output!("The rectangle decribed by {:?} has:", rect);
+        output!("\tLength: {} units", length);
+        output!("\tWidth: {} units", width);
+        output!("\tArea: {} square units", width * length);
     }
     rect_area(rectangle);
 
-    fn square(lower_left: Point, size: f32) -> Rectangle {
-        let p1 = Point {
-            x: lower_left.x,
-            y: lower_left.y,
-        };
-        let p2 = Point {
-            x: lower_left.x + size,
-            y: lower_left.y + size,
-        };
-        let rect = Rectangle { p1: p1, p2: p2 };
-        println!("\nCreated a new square (Rectangle):\n{:?}", rect);
-        return rect;
-    }
     let square = square(
         Point {
             x: 10.5f32,
@@ -79,87 +85,82 @@ fn c_structs() {
         },
         3.1415926f32,
     );
+    output!("\nCreated a new square (Rectangle):\n{:?}", square);
     rect_area(square);
 }
 
 fn unit_structs() {
-    helpers::section_subtitle(format!("Unit Structs"));
-    println!("Fieldless structs, mostly for generics"); // TODO: Explain generics
+    narrate!("Fieldless structs, mostly for generics"); // TODO: Explain generics
     #[derive(Debug)]
     struct Nil;
     let nil = Nil;
-    println!("struct Nil; nil = Nil; nil => {:?}", nil);
+    output!("struct Nil; nil = Nil; nil => {:?}", nil);
 }
 
 fn tuple_structs() {
-    helpers::section_subtitle(format!("Tuple Structs"));
-    println!("These are basically just tuples with names");
-
-    struct Pair(i32, f32);
-    let pair = Pair(1, 0.1);
-    println!("\nWith:");
-    println!("\tstruct Pair(i32, f32)");
-    println!("\tpair = Pair(1, 0.1)");
-    println!("\tpair.0 = {:?}; pair.1 = {:?}", pair.0, pair.1);
-
-    let Pair(integer, decimal) = pair;
-    println!("\ntuple structs can also be destructured with let:");
-    println!("\t`let Pair(integer, decimal) = pair`");
-    println!("\tinteger = {:?}; decimal = {:?}", integer, decimal);
+    narrate!("These are basically just tuples with names");
+
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        struct Pair(i32, f32);
+        let pair = Pair(1, 0.1);
+    }
+    output!("\tpair.0 = {:?}; pair.1 = {:?}", pair.0, pair.1);
+
+    narrate!("\ntuple structs can also be destructured with let:");
+    show_and_run! { SOURCE;
+        let Pair(integer, decimal) = pair;
+    }
+    output!("\tinteger = {:?}; decimal = {:?}", integer, decimal);
 }
 
 fn structures() {
-    helpers::section_title(format!("Structures"));
-    println!("There are 3 types of structs that can be created with the struct keyword");
-    c_structs();
-    unit_structs();
-    tuple_structs();
+    narrate!("There are 3 types of structs that can be created with the struct keyword");
 }
 
+// Only some of the variants are ever made
+#[allow(dead_code)]
 fn use_enum() {
-    helpers::section_subtitle(format!("use-ing Enums"));
-    println!("The use declaration can bue used to bind enums to a more local scope");
+    narrate!("The use declaration can bue used to bind enums to a more local scope");
 
-    enum Status {
-        Rich,
-        Poor,
-    }
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        enum Status {
+            Rich,
+            Poor,
+        }
 
-    enum Work {
-        Civilian,
-        Soldier,
+        enum Work {
+            Civilian,
+            Soldier,
+        }
     }
 
-    println!("\nWith:");
-    println!("\tenum Status {{ Rich, Poor }}");
-    println!("\tenum Work {{ Civilian, Soldier }}");
-
-    use Status::{Poor, Rich};
-    println!("Explicitly use needed items => use Status::{{Poor, Rich}};");
-    use Work::*;
-    println!("Automatically use each name => user Work::*;");
-
-    let status = Poor;
-    let work = Civilian;
+    narrate!("Explicitly use needed items, or automatically use each name with *:");
+    show_and_run! { SOURCE;
+        use Status::{Poor, Rich};
+        use Work::*;
+    }
 
-    println!("\nAnd with:");
-    println!("\tlet status = Poor");
-    println!("\tlet work = Civilian");
+    narrate!("\nAnd with:");
+    show_and_run! { SOURCE;
+        let status = Poor;
+        let work = Civilian;
+    }
 
     match status {
-        Rich => println!("The rich have lots of money!"),
-        Poor => println!("The poor don't have any money..."),
+        Rich => output!("The rich have lots of money!"),
+        Poor => output!("The poor don't have any money..."),
     }
 
     match work {
-        Civilian => println!("Civilians work!"),
-        Soldier => println!("Soldiers fight!"),
+        Civilian => output!("Civilians work!"),
+        Soldier => output!("Soldiers fight!"),
     }
 }
 
 fn c_like_enums() {
-    helpers::section_subtitle(format!("C-like Enums"));
-    println!("enums can be used like C-like enums");
+    narrate!("enums can be used like C-like enums");
 
     enum Number {
         Zero,
@@ -171,24 +172,23 @@ fn c_like_enums() {
         Green = 0x00ff00,
         Blue = 0x0000ff,
     }
-    println!("\nWith:");
-    println!("\timplicit discriminator (starts at 0) => enum Number {{ Zero, One, Two }}");
-    print!("\texplicit discriminator => ");
-    println!("enum Color {{ Red = 0xff0000, Green = 0x00ff00, Blue = 0x0000ff }}");
+    narrate!("\nWith:");
+    listing!(ignore; "implicit discriminator (starts at 0) => enum Number {{ Zero, One, Two }}");
+    output_inline!("\texplicit discriminator => ");
+    narrate!("enum Color {{ Red = 0xff0000, Green = 0x00ff00, Blue = 0x0000ff }}");
 
-    println!("Number::Zero is {}", Number::Zero as i32);
-    println!("Number::One is {}", Number::One as i32);
-    println!("Number::Two is {}", Number::Two as i32);
+    output!("Number::Zero is {}", Number::Zero as i32);
+    output!("Number::One is {}", Number::One as i32);
+    output!("Number::Two is {}", Number::Two as i32);
 
     // NOTE: Best programming poem yet
-    println!("Roses are #{:06x}", Color::Red as i32);
-    println!("Violets are #{:06x}", Color::Blue as i32);
-    println!("Color::Green is {}", Color::Green as i32);
+    output!("Roses are #{:06x}", Color::Red as i32);
+    output!("Violets are #{:06x}", Color::Blue as i32);
+    output!("Color::Green is {}", Color::Green as i32);
 }
 
 fn linked_list() {
     use List::{ Cons, Nil };
-    helpers::section_title(format!("Testcase: Linked-List"));
     enum List { Cons(u32, Box<List>), Nil }
     impl List {
         fn new() -> List { Nil }
@@ -207,7 +207,7 @@ fn linked_list() {
                     format!("{}, {}", head, tail.stringify())
                 },
                 Nil => {
-                    format!("Nil")
+                    "Nil".to_string()
                 },
             }
         }
@@ -219,20 +219,19 @@ fn linked_list() {
         list = list.prepend(x);
     }
 
-    println!("\nWith:");
-    println!("\tuse List::{{ Cons, Nil }};");
-    println!("\tList {{ Cons(u32, Box<List>), Nil }}");
-    println!("\timpl List {{ new(), prepend(u32), len(), stringify() }}");
-    println!("let mut list = List::new();");
-    println!("did this a few times => list = list.prepend(#u32);");
-    println!("The linked List has length: {}", list.len());
-    println!("The linked list is: {}", list.stringify());
+    narrate!("\nWith:");
+    listing!("use List::{{ Cons, Nil }};");
+    listing!("enum List {{ Cons(u32, Box<List>), Nil }}");
+    listing!(ignore; "impl List {{ new(), prepend(u32), len(), stringify() }}");
+    narrate!("let mut list = List::new();");
+    narrate!("did this a few times => list = list.prepend(#u32);");
+    output!("The linked List has length: {}", list.len());
+    output!("The linked list is: {}", list.stringify());
 }
 
 fn enumerators() {
-    helpers::section_title(format!("Enumerators"));
-    println!("The enum keyword allows for the creation of a type of a variant");
-    println!("Any variant that is valid as a struct is valid as an enum");
+    narrate!("The enum keyword allows for the creation of a type of a variant");
+    narrate!("Any variant that is valid as a struct is valid as an enum");
 
     enum WebEvent {
         // Unit-like
@@ -247,11 +246,11 @@ fn enumerators() {
 
     fn inspect(event: WebEvent) {
         match event {
-            WebEvent::PageLoad => println!("\tpage loaded"),
-            WebEvent::PageUnload => println!("\tpage unloaded"),
-            WebEvent::KeyPress(c) => println!("\tpressed '{}'", c),
-            WebEvent::Paste(s) => println!("\tpasted \"{}\"", s),
-            WebEvent::Click { x, y } => println!("\tclicked at x={}, y={}", x, y),
+            WebEvent::PageLoad => output!("\tpage loaded"),
+            WebEvent::PageUnload => output!("\tpage unloaded"),
+            WebEvent::KeyPress(c) => output!("\tpressed '{}'", c),
+            WebEvent::Paste(s) => output!("\tpasted \"{}\"", s),
+            WebEvent::Click { x, y } => output!("\tclicked at x={}, y={}", x, y),
         }
     }
 
@@ -267,52 +266,87 @@ fn enumerators() {
     inspect(load);
     inspect(unload);
 
-    println!("This is mostly just to have a complete example with no comments");
-    println!("It's pretty straight-forward, so read the code if you're interested");
-    println!("More examples are coming");
-
-    use_enum();
-    c_like_enums();
-    linked_list();
+    narrate!("This is mostly just to have a complete example with no comments");
+    narrate!("It's pretty straight-forward, so read the code if you're interested");
+    narrate!("More examples are coming");
 }
 
 fn constants() {
-    helpers::section_title(format!("Constants"));
-    println!("Rust has two constant types:");
-    println!("\tconst - An unchangeable value");
-    println!("\tstatic - A (possibly) mutable variable with a 'static lifetime");
-    println!("\t\tAccessing or modifying a mutabe static variable is unsafe.");
-
-    static LANGUAGE: &str = "Rust";
-    const THRESHOLD: i32 = 10;
+    narrate!("Rust has two constant types:");
+    narrate!("\tconst - An unchangeable value");
+    narrate!("\tstatic - A (possibly) mutable variable with a 'static lifetime");
+    narrate!("\t\tAccessing or modifying a mutabe static variable is unsafe.");
+
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        static LANGUAGE: &str = "Rust";
+        const THRESHOLD: i32 = 10;
+
+        fn is_big(n: i32) -> bool {
+            n > THRESHOLD
+        }
 
-    fn is_big(n: i32) -> bool {
-        n > THRESHOLD
+        let n = 16;
     }
+    output!("This is {}", LANGUAGE);
+    output!("The threshold is {}", THRESHOLD);
+    output!("{} is {}", n, if is_big(n) { "big" } else { "small" });
+}
 
-    let n = 16;
-
+fn intro() {
+    narrate!("Rust has two main custom data types:");
+    narrate!("\tstruct - a structure");
+    narrate!("\tenum - an enumerator");
 
-    println!("\nWith:");
-    println!("\tstatic LANGUAGE: &str = \"{}\";", LANGUAGE);
-    println!("\tconst THRESHOLD: i32 = {};", THRESHOLD);
-    println!("\tfn is_big(n: i32) -> bool {{ n > THRESHOLD }}");
-    println!("\tlet n = {};", n);
-    println!("This is {}", LANGUAGE);
-    println!("The threshold is {}", THRESHOLD);
-    println!("{} is {}", n, if is_big(n) { "big" } else { "small" });
+    narrate!("\nConstants can also be created using the const or static keywords");
+}
 
+#[allow(clippy::approx_constant)]
+fn square_rectangle() -> String {
+    format!("{:?}\n", square(Point { x: 10.5, y: 10.1 }, 3.1415926))
 }
 
-pub fn run() {
-    helpers::example_title(format!("Example {}: {}", 3, "Custom Types"));
-    println!("Rust has two main custom data types:");
-    println!("\tstruct - a structure");
-    println!("\tenum - an enumerator");
+pub struct CustomTypes;
+
+const SECTIONS: &[Section] = &[
+    Section::new("structures", "Structures", structures).with_subsections(&[
+        Section::new("c_structs", "C Structs", c_structs),
+        Section::new("unit_structs", "Unit Structs", unit_structs),
+        Section::new("tuple_structs", "Tuple Structs", tuple_structs),
+    ]),
+    Section::new("enumerators", "Enumerators", enumerators).with_subsections(&[
+        Section::new("use_enum", "use-ing Enums", use_enum),
+        Section::new("c_like_enums", "C-like Enums", c_like_enums),
+    ]),
+    Section::new("linked_list", "Testcase: Linked-List", linked_list),
+    Section::new("constants", "Constants", constants),
+];
+
+const EXERCISES: &[Exercise] = &[Exercise {
+    slug: "square",
+    title: "Make a square Rectangle",
+    stub: include_str!("../exercises/square.rs"),
+    expected: square_rectangle,
+}];
+
+impl Chapter for CustomTypes {
+    fn slug(&self) -> &'static str {
+        "custom-types"
+    }
 
-    println!("\nConstants can also be created using the const or static keywords");
+    fn title(&self) -> &'static str {
+        "Custom Types"
+    }
 
-    structures();
-    enumerators();
-    constants();
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
 }
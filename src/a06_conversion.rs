@@ -1,20 +1,24 @@
-use super::helpers;
+use super::chapter::{question, Chapter, Question, Section};
+use super::panics;
+use super::render::{narrate, output, show_and_run};
 use std::convert::From;
 use std::fmt;
 use typename::TypeName;
 
-fn from_and_into() {
-    helpers::section_title(format!("From and Into Traits"));
-    println!("From and Into traits are inherently linked");
-    println!("If you can convert Into type A, you should be able to convert From type A");
+const SOURCE: &str = include_str!("a06_conversion.rs");
 
-    let my_str = "hello";
-    let my_string = String::from(my_str);
+// Number's value is only ever shown with Debug
+#[allow(dead_code)]
+fn from_and_into() {
+    narrate!("From and Into traits are inherently linked");
+    narrate!("If you can convert Into type A, you should be able to convert From type A");
 
-    println!("\nWith:");
-    println!("\tlet my_str = \"{}\";", my_str);
-    println!("\tmy_string = String::from(my_str)");
-    println!(
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        let my_str = "hello";
+        let my_string = String::from(my_str);
+    }
+    output!(
         "my_str => {}: {};\nmy_string => {}: {}",
         my_str,
         TypeName::type_name_of(my_str),
@@ -22,71 +26,110 @@ fn from_and_into() {
         TypeName::type_name_of(&my_string)
     );
 
-    #[derive(Debug)]
-    struct Number {
-        value: i32,
-    }
-    impl From<i32> for Number {
-        fn from(item: i32) -> Self {
-            Number { value: item }
+    narrate!("\nYou can define the same type of conversion for a custom type");
+    narrate!("With:");
+    show_and_run! { SOURCE;
+        #[derive(Debug)]
+        struct Number {
+            value: i32,
         }
+        impl From<i32> for Number {
+            fn from(item: i32) -> Self {
+                Number { value: item }
+            }
+        }
+        let num = Number::from(30);
+    }
+    output!("num is {:?}", num);
+
+    narrate!("\nYou get Into for free by defining From");
+    narrate!("With:");
+    show_and_run! { SOURCE;
+        let int = 5;
+        let num2: Number = int.into();
     }
-    let num = Number::from(30);
-    println!("\nYou can define the same type of conversion for a custom type");
-    println!("With:");
-    println!("\tstruct Number {{ value: i32 }}");
-    println!("\timpl From<i32> for Number {{\n\t\tfn from(item: i32) -> Self {{\n\t\t\tNumber {{ value: item }}\n\t\t}}\n\t}}");
-    println!("\tnum = Number::from(30)");
-    println!("num is {:?}", num);
-
-    let int = 5;
-    let num2: Number = int.into();
-    println!("\nYou get Into for free by defining From");
-    println!("With:");
-    println!("\tint = {}", int);
-    println!("\tnum2: Number = int.into()");
-    println!("num2 is {:?}", num2);
+    output!("num2 is {:?}", num2);
 }
 
+#[allow(clippy::to_string_in_format_args)]
 fn to_and_from_strings() {
-    helpers::section_title(format!("To- and From-Strings"));
-    println!("Converting to a String is simple as well, you just need to implement ToString");
-    println!("But rather than doing it directly it's better to implement the fmt::Display trait");
-    println!("Doing it this way automatically provided ToString plus allows printing with print!/format! as shown in Example 1");
-
-    struct Circle { radius: f32 }
-    impl fmt::Display for Circle {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "Circle of radius {}", self.radius)
+    narrate!("Converting to a String is simple as well, you just need to implement ToString");
+    narrate!("But rather than doing it directly it's better to implement the fmt::Display trait");
+    narrate!("Doing it this way automatically provided ToString plus allows printing with print!/format! as shown in Example 1");
+
+    narrate!("\nWith:");
+    let circle = show_and_run!(SOURCE; {
+        struct Circle {
+            radius: f32,
+        }
+        impl fmt::Display for Circle {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "Circle of radius {}", self.radius)
+            }
         }
+        Circle { radius: 6.1 }
+    });
+    output!("{}", circle.to_string());
+
+    narrate!("\nOne of the more common types is to convert a string to a number");
+    narrate!("You can use the ToString trait to impl this on custom types");
+    narrate!("There are two approaches here: without type inference, or with 'turbofish' syntax");
+    narrate!("With:");
+    show_and_run! { SOURCE;
+        let parsed: i32 = "5".parse().unwrap();
+        let turbofish_parsed = "10".parse::<i32>().unwrap();
+        let sum = parsed + turbofish_parsed;
     }
-    let circle = Circle { radius: 6.1 };
-    println!("\nWith:");
-    println!("\tstruct Circle {{ radius: f32 }}");
-    println!("\timpl fmt::Display for Circle {{\n\t\tfn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{\n\t\t\twrite!(f, \"Circle of radius {{}}\", self.radius)\n\t}}");
-    println!("\tcircle = Circle {{ radius: 6.1 }}");
-    println!("{}", circle.to_string());
-
-    let parsed: i32 = "5".parse().unwrap();
-    let turbofish_parsed = "10".parse::<i32>().unwrap();
-    let sum = parsed + turbofish_parsed;
-
-    println!("\nOne of the more common types is to convert a string to a number");
-    println!("You can use the ToString trait to impl this on custom types");
-    println!("There are two approaches here: without type inference, or with 'turbofish' syntax");
-    println!("With:");
-    println!("\tparsed: i32 = \"5\".parse().unwrap()");
-    println!("\tturbofish_parsed = \"10\".parse::<i32>().unwrap()");
-    println!("\tsum = parsed + turbofish_parsed");
-    println!("{} + {} = {}", parsed, turbofish_parsed, sum);
+    output!("{} + {} = {}", parsed, turbofish_parsed, sum);
+
+    narrate!("\nparse returns a Result, and unwrap panics when it's an Err:");
+    panics::show(|| {
+        show_and_run! { SOURCE;
+            let parsed: i32 = "five".parse().unwrap();
+            output!("{}", parsed);
+        }
+    });
+}
+
+fn intro() {
+    narrate!("Rust converts between types using traits.");
+    narrate!("Generic conversions use From and Into traits");
+    narrate!("However, specific cases, such as Strings, have more specific traits");
+}
+
+fn quiz() -> Vec<Question> {
+    vec![
+        question!("{}", "5".parse::<i32>().unwrap() + "10".parse::<i32>().unwrap()),
+        question!("{}", String::from("hello").len()),
+        question!("{}", 6.1.to_string()),
+    ]
 }
 
-pub fn run() {
-    helpers::example_title(format!("Example {}: {}", 6, "Conversion"));
-    println!("Rust converts between types using traits.");
-    println!("Generic conversions use From and Into traits");
-    println!("However, specific cases, such as Strings, have more specific traits");
+pub struct Conversion;
+
+const SECTIONS: &[Section] = &[
+    Section::new("from_and_into", "From and Into Traits", from_and_into),
+    Section::new("to_and_from_strings", "To- and From-Strings", to_and_from_strings),
+];
 
-    from_and_into();
-    to_and_from_strings();
+impl Chapter for Conversion {
+    fn slug(&self) -> &'static str {
+        "conversion"
+    }
+
+    fn title(&self) -> &'static str {
+        "Conversion"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn quiz(&self) -> Vec<Question> {
+        quiz()
+    }
 }
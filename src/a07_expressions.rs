@@ -1,22 +1,56 @@
-use super::helpers;
-
-pub fn run(){
-    helpers::example_title(format!("Exmaple 7: Expressions"));
-    println!("A Rust program is mostly a series of statements");
-    println!("Statements in Rust include declaring a variable binding, and any expression that ends with a ;");
-    println!("Blocks ({{}}) are expressions too - the last expression in the block will be returned");
-    println!("If the last line ends with ; then () will be returned");
-
-    let x = 5u32;
-    let y = {
-        let x_squared = x * x;
-        let x_cubed = x_squared * x;
-        x_cubed + x_squared + x
-    };
-    let z = { 2 * x; };
-    println!("\nWith:");
-    println!("\tlet x = 5u32;");
-    println!("\tlet y = {{\n\t\tlet x_squared = x * x;\n\t\tlet x_cubed = x_squared * x;\n\t\tx_cubed + x_squared + x\n\t}};");
-    println!("\tlet z = {{ 2 * x; }};");
-    println!("x: {:?}, y: {:?}, z: {:?}", x, y, z);
+use super::chapter::{question, Chapter, Question, Section};
+use super::render::{narrate, output, show_and_run};
+
+const SOURCE: &str = include_str!("a07_expressions.rs");
+
+#[allow(clippy::let_unit_value, clippy::no_effect, unused_must_use)]
+fn intro() {
+    narrate!("A Rust program is mostly a series of statements");
+    narrate!("Statements in Rust include declaring a variable binding, and any expression that ends with a ;");
+    narrate!("Blocks ({{}}) are expressions too - the last expression in the block will be returned");
+    narrate!("If the last line ends with ; then () will be returned");
+
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        let x = 5u32;
+        let y = {
+            let x_squared = x * x;
+            let x_cubed = x_squared * x;
+            x_cubed + x_squared + x
+        };
+        let z = { 2 * x; };
+    }
+    output!("x: {:?}, y: {:?}, z: {:?}", x, y, z);
+}
+
+#[allow(clippy::unused_unit, clippy::no_effect, unused_must_use)]
+fn quiz() -> Vec<Question> {
+    vec![
+        question!("{:?}", { 2 * 5u32; }),
+        question!("{:?}", { let x = 5u32; x * x * x + x * x + x }),
+    ]
+}
+
+pub struct Expressions;
+
+impl Chapter for Expressions {
+    fn slug(&self) -> &'static str {
+        "expressions"
+    }
+
+    fn title(&self) -> &'static str {
+        "Expressions"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[]
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn quiz(&self) -> Vec<Question> {
+        quiz()
+    }
 }
\ No newline at end of file
@@ -1,140 +1,142 @@
-use super::helpers;
+use super::chapter::{question, Chapter, CompileFail, Question, Section};
+use super::diagnostics;
+use super::panics;
+use super::render::{listing, narrate, output, output_inline, show_and_run};
+
+const SOURCE: &str = include_str!("a09_functions.rs");
 
 fn fizzbuzz_to(n: u32) {
     fn is_divisible_by(n: u32, by_n: u32) -> bool {
         if n == 0 {
             return false;
         } // edge case
-        n % by_n == 0
+        n.is_multiple_of(by_n)
     }
     fn fizzbuzz(n: u32) {
         if is_divisible_by(n, 3) {
-            print!("Fizz");
+            output_inline!("Fizz");
         }
         if is_divisible_by(n, 5) {
-            print!("Buzz");
+            output_inline!("Buzz");
         }
         if !is_divisible_by(n, 3) && !is_divisible_by(n, 5) {
-            print!("{}", n);
+            output_inline!("{}", n);
         }
-        print!(", ");
+        output_inline!(", ");
     }
     for i in 1..=n {
         fizzbuzz(i);
     }
-    println!("... And that's FizzBuzz again");
+    narrate!("... And that's FizzBuzz again");
 }
 
 fn methods() {
-    helpers::section_title(format!("Methods"));
-    println!("Methods are functions attached to objects, like every other programming language");
-    println!(
+    narrate!("Methods are functions attached to objects, like every other programming language");
+    narrate!(
         "Methods have access to the object's data and methods with the `self` keyword, like Python"
     );
-    println!("\t(but as &self, which is syntactic sugar for e.g. self: &Point)");
-    println!("\nMethods are defined in an `impl` block related to the object");
-    println!("You can implement static methods that don't need to be called by an instance like:");
-    println!("\timpl Point {{ fn new(x: f64, y: f64) -> Point {{ ... }}");
-    println!("\t\tthen call with :: like:\tPoint::new(3.0, 4.0)");
-    println!("You can implement instance methods like:");
-    println!("\timpl Rectangle {{\n\t\tfn area(&self) -> f64 {{ ... }}\n\t}}");
-    println!("\timpl Rectangle {{\n\t\tfn translate(&mut self) {{ ... }}\n\t}}");
-    println!(
+    narrate!("\t(but as &self, which is syntactic sugar for e.g. self: &Point)");
+    narrate!("\nMethods are defined in an `impl` block related to the object");
+    narrate!("You can implement static methods that don't need to be called by an instance like:");
+    listing!(ignore; "impl Point {{ fn new(x: f64, y: f64) -> Point {{ ... }}");
+    narrate!("\t\tthen call with :: like:\tPoint::new(3.0, 4.0)");
+    narrate!("You can implement instance methods like:");
+    listing!(ignore; "impl Rectangle {{\n\tfn area(&self) -> f64 {{ ... }}\n}}");
+    listing!(ignore; "impl Rectangle {{\n\tfn translate(&mut self) {{ ... }}\n}}");
+    narrate!(
         "\t\tthen call with . like:\tRectangle.area() (self is implicitly passed, like Python)"
     );
 
-    println!("\nWith:");
-    println!("\tstruct Point {{ x: f64, y: f64 }}");
-    println!("\timpl Point {{\n\t\tfn origin() -> Point {{ ... }}\n\t\tfn new(x:f64, y: f64) -> Point {{ ... }}\n\t}}");
-    println!("\tstruct Rectangle {{ p1: Point, p2: Point }}");
-    println!("\timpl Rectangle {{\n\t\tfn area(&self) -> f64 {{ ... }}\n\t\tfn perimiter(&self) -> f64 {{ ... }}\n\t\tfn translate(&mut self, x: f64, y:f64) {{ ... }}\n\t}}");
-    println!("\tstruct Pair ( Box<i32>, Box<i32> );");
-    println!("\t");
-
-    struct Point {
-        x: f64,
-        y: f64,
-    }
-
-    impl Point {
-        fn origin() -> Point {
-            Point { x: 0.0, y: 0.0 }
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        struct Point {
+            x: f64,
+            y: f64,
         }
-        fn new(x: f64, y: f64) -> Point {
-            Point { x: x, y: y }
-        }
-    }
 
-    struct Rectangle {
-        p1: Point,
-        p2: Point,
-    }
-
-    impl Rectangle {
-        fn area(&self) -> f64 {
-            let Point { x: x1, y: y1 } = self.p1;
-            let Point { x: x2, y: y2 } = self.p2;
-
-            ((x1 - x2) * (y1 - y2)).abs()
+        impl Point {
+            fn origin() -> Point {
+                Point { x: 0.0, y: 0.0 }
+            }
+            fn new(x: f64, y: f64) -> Point {
+                Point { x, y }
+            }
         }
 
-        fn perimeter(&self) -> f64 {
-            let Point { x: x1, y: y1 } = self.p1;
-            let Point { x: x2, y: y2 } = self.p2;
-            2.0 * ((x2 - x1).abs() + (y2 - y1).abs())
+        struct Rectangle {
+            p1: Point,
+            p2: Point,
         }
 
-        fn translate(&mut self, x: f64, y: f64) {
-            self.p1.x += x;
-            self.p2.x += x;
-            self.p1.y += y;
-            self.p2.y += y;
+        impl Rectangle {
+            fn area(&self) -> f64 {
+                let Point { x: x1, y: y1 } = self.p1;
+                let Point { x: x2, y: y2 } = self.p2;
+
+                ((x1 - x2) * (y1 - y2)).abs()
+            }
+
+            fn perimeter(&self) -> f64 {
+                let Point { x: x1, y: y1 } = self.p1;
+                let Point { x: x2, y: y2 } = self.p2;
+                2.0 * ((x2 - x1).abs() + (y2 - y1).abs())
+            }
+
+            fn translate(&mut self, x: f64, y: f64) {
+                self.p1.x += x;
+                self.p2.x += x;
+                self.p1.y += y;
+                self.p2.y += y;
+            }
         }
-    }
 
-    struct Pair(Box<i32>, Box<i32>);
-    impl Pair {
-        fn destroy(self) {
-            let Pair(first, second) = self;
-            println!("Destroying {} and {}", first, second);
-            // first and second go out of scope and get freed
+        struct Pair(Box<i32>, Box<i32>);
+        impl Pair {
+            fn destroy(self) {
+                let Pair(first, second) = self;
+                output!("Destroying {} and {}", first, second);
+                // first and second go out of scope and get freed
+            }
         }
     }
-    let rectangle = Rectangle {
-        p1: Point::origin(),
-        p2: Point::new(3.0, 4.0),
-    };
-
-    println!("Rectangle perimeter: {}", rectangle.perimeter());
-    println!("Rectangle area: {}", rectangle.area());
-    // rectangle.translate(1.0, 1.0); // Needs to be mutable as that's what the fn needs
 
-    let mut square = Rectangle {
-        p1: Point::origin(),
-        p2: Point::new(1.0, 1.0),
-    };
-    square.translate(1.0, 1.0);
-
-    let pair = Pair(Box::new(1), Box::new(2));
-    pair.destroy();
-    // pair.destroy() // won't work because the pair was already consumed
+    narrate!("\nThey're used like this:");
+    show_and_run! { SOURCE;
+        let rectangle = Rectangle {
+            p1: Point::origin(),
+            p2: Point::new(3.0, 4.0),
+        };
+
+        output!("Rectangle perimeter: {}", rectangle.perimeter());
+        output!("Rectangle area: {}", rectangle.area());
+        // rectangle.translate(1.0, 1.0); // Needs to be mutable as that's what the fn needs
+
+        let mut square = Rectangle {
+            p1: Point::origin(),
+            p2: Point::new(1.0, 1.0),
+        };
+        square.translate(1.0, 1.0);
+
+        let pair = Pair(Box::new(1), Box::new(2));
+        pair.destroy();
+        // pair.destroy() // won't work because the pair was already consumed
+    }
 }
 fn closures() {
-    helpers::section_title(format!("Closures"));
-    println!("Also know as lambdas, closures are functions that capture the enclosing environment, e.g.:");
-    println!("\tminimal: |val| val + x;");
-    println!("\tmaximal: let thing = |val: i32| -> i32 {{ val +1; val + x }}");
-    println!("\tsuper-minimal: || 1;");
-
-    println!("\nCalling a closure is exactly like calling a function, except:");
-    println!("\tdon't use the fn keyword and they're nameless, so are either anonymous or assigned to a reference (with `let`)");
-    println!("\tinput and return types can be inferred");
-    println!("\tinput variable names must be specified");
-    println!("\t|| around input parameters instead of ()");
-    println!(
+    narrate!("Also know as lambdas, closures are functions that capture the enclosing environment, e.g.:");
+    listing!(ignore; "minimal: |val| val + x;");
+    listing!(ignore; "maximal: let thing = |val: i32| -> i32 {{ val +1; val + x }}");
+    listing!(ignore; "super-minimal: || 1;");
+
+    narrate!("\nCalling a closure is exactly like calling a function, except:");
+    narrate!("\tdon't use the fn keyword and they're nameless, so are either anonymous or assigned to a reference (with `let`)");
+    narrate!("\tinput and return types can be inferred");
+    narrate!("\tinput variable names must be specified");
+    narrate!("\t|| around input parameters instead of ()");
+    narrate!(
         "\tblock delineation ({{ ... }}) only necessary if the closure has more than 1 expression"
     );
-    println!("\tclosures have the ability to capture outer environment variables");
+    narrate!("\tclosures have the ability to capture outer environment variables");
 
     fn function(i: i32) -> i32 {
         i + i
@@ -145,48 +147,41 @@ fn closures() {
     let one = || 1;
 
     let i = 1;
-    println!("function: {}", function(i));
-    println!("closure_annotated: {}", closure_annotated(i));
-    println!("closure_inferred: {}", closure_inferred(i));
-    println!("closure returning 1: {}", one());
-
-    capturing();
-    as_input_parameters();
-    type_anonymity();
-    input_functions();
-    as_output_parameters();
-    examples_in_std();
+    output!("function: {}", function(i));
+    output!("closure_annotated: {}", closure_annotated(i));
+    output!("closure_inferred: {}", closure_inferred(i));
+    output!("closure returning 1: {}", one());
 }
 
+#[allow(clippy::useless_vec)]
 fn capturing() {
-    helpers::section_subtitle(format!("Capturing"));
-    println!("Closures are flexible to the max and will try really hard to make the closure work without annotation");
-    println!("That means they'll flexibly adapt to the use case, sometimes moving and sometimes borrowing variables");
-    println!(
+    narrate!("Closures are flexible to the max and will try really hard to make the closure work without annotation");
+    narrate!("That means they'll flexibly adapt to the use case, sometimes moving and sometimes borrowing variables");
+    narrate!(
         "There's an order of specificity, basically attempting to limit access when possible:"
     );
-    println!("\tby (borrowed) reference: &T");
-    println!("\tby (borrowed) mutable reference: &mut T");
-    println!("\tby (moved) value: T");
-    println!("You can also force the value to be moved with the `move` keyword before the pipes");
-    println!("\t");
+    narrate!("\tby (borrowed) reference: &T");
+    narrate!("\tby (borrowed) mutable reference: &mut T");
+    narrate!("\tby (moved) value: T");
+    narrate!("You can also force the value to be moved with the `move` keyword before the pipes");
+    narrate!("\t");
 
-    println!("\nLet's look at some examples:");
+    narrate!("\nLet's look at some examples:");
 
-    println!("\n1. &T - reference");
-    println!("\tA function like `println!` only needs to have the &str while it operates");
+    narrate!("\n1. &T - reference");
+    narrate!("\tA function like `println!` only needs to have the &str while it operates");
     let color = "green";
-    let what_color = || println!("color is: {}", color); // by reference: &T
+    let what_color = || output!("color is: {}", color); // by reference: &T
     what_color();
     what_color();
 
-    println!("\n2. &mut T - mutable reference");
-    println!("\tIn the case that you're e.g. incrementing a variable, it all needs to be mutable");
+    narrate!("\n2. &mut T - mutable reference");
+    narrate!("\tIn the case that you're e.g. incrementing a variable, it all needs to be mutable");
     let mut count = 0; // mutable variable, and mutable closure
     let mut increment = || {
         // by mutable reference: &mut T
         count += 1;
-        println!("count is: {}", count);
+        output!("count is: {}", count);
     };
     increment();
     increment();
@@ -195,80 +190,86 @@ fn capturing() {
     increment();
     increment();
 
-    println!("\n3. T - moved by value");
-    println!("\tmem:::drop requires `T` so this closure can only take by value");
-    println!("\tA copy type would copy into the closure, leaving the original untouched");
-    println!("A non-copy type must move into the closure itself");
+    narrate!("\n3. T - moved by value");
+    narrate!("\tmem:::drop requires `T` so this closure can only take by value");
+    narrate!("\tA copy type would copy into the closure, leaving the original untouched");
+    narrate!("A non-copy type must move into the closure itself");
     use std::mem;
     let movable = Box::new(3);
     let consume = || {
-        println!("movable: {}", movable);
+        output!("movable: {}", movable);
         mem::drop(movable);
     };
 
     consume();
-    // consume(); // won't work
+    narrate!("\nIt's gone after that, so the closure can't be called again:");
+    diagnostics::show(&CONSUMED_TWICE);
 
-    println!("\n4. Force move with `move`");
+    narrate!("\n4. Force move with `move`");
     let haystack = vec![1, 2, 3];
     let contains = move |needle| haystack.contains(needle);
-    println!("haystack contains &1: {}", contains(&1));
-    println!("haystack contains &4: {}", contains(&4));
-    // println!("haystack contains {} elements", haystack.len()); // Won't work - haystack was moved
+    output!("haystack contains &1: {}", contains(&1));
+    output!("haystack contains &4: {}", contains(&4));
+    narrate!("\nhaystack was moved into the closure, so it can't be used outside it any more:");
+    diagnostics::show(&MOVED_HAYSTACK);
 }
 
 fn as_input_parameters() {
-    helpers::section_subtitle(format!("Closures as input parameters"));
-    println!("While Rust's closures choose how to capture variables quite well, ambiguity in function declarations is not valid");
-    println!("When taking a closure as an input parameter the closure's complete type must be annotated, using these traits:");
-    println!("\tFn - captures by reference &T");
-    println!("\tFnMut - captures by mutable reference &mut T");
-    println!("\tFnOnce - captures by value T");
-    println!("These traits are applied prefentially in that order, and the compiler tries to caputer variables in the least restrictive manner");
-    println!("So Fn will only capture by reference, but FnOnce will try to capture &T, &mut T and only T if necessary");
-
-    println!("\nSyntax examples:\n\tfn apply<F>(f: F) where F: FnOnce() {{ ... }}\n\tfn apply_to_3<F>(f: F) -> i32 where F: Fn(i32) -> i32 {{ ... }}");
-    println!("\n\tfn some_function<T, U>(t: T, u: U) -> i32\n\t\twhere T: Display + Clone,\n\t\t\tU: Clone + Debug {{ ... }}");
-
-    use std::mem;
-
-    fn apply<F>(f: F)
-    where
-        F: FnOnce(),
-    {
-        f();
-    } // This syntax is fucky too
-    fn apply_to_3<F>(f: F) -> i32
-    where
-        F: Fn(i32) -> i32,
-    {
-        f(3)
+    narrate!("While Rust's closures choose how to capture variables quite well, ambiguity in function declarations is not valid");
+    narrate!("When taking a closure as an input parameter the closure's complete type must be annotated, using these traits:");
+    narrate!("\tFn - captures by reference &T");
+    narrate!("\tFnMut - captures by mutable reference &mut T");
+    narrate!("\tFnOnce - captures by value T");
+    narrate!("These traits are applied prefentially in that order, and the compiler tries to caputer variables in the least restrictive manner");
+    narrate!("So Fn will only capture by reference, but FnOnce will try to capture &T, &mut T and only T if necessary");
+
+    narrate!("\nSyntax examples:");
+    show_and_run! { SOURCE;
+        use std::mem;
+
+        fn apply<F>(f: F)
+        where
+            F: FnOnce(),
+        {
+            f();
+        } // This syntax is fucky too
+        fn apply_to_3<F>(f: F) -> i32
+        where
+            F: Fn(i32) -> i32,
+        {
+            f(3)
+        }
     }
-    let greeting = "hello";
-    let mut farewell = "goodbye".to_owned();
-    // capture greeting by reference and farewell by value
-    let diary = || {
-        println!("I said {}", greeting); // Requires Fn
-        farewell.push_str("!!!"); // Requires FnMut to be captured as mutable reference
-        println!("Then I screamed {}", farewell);
-        println!("Now I can sleep. Zzzz.");
-
-        mem::drop(farewell); // Manually calling mem::drop requires farewell to e captured by value
-    };
-    apply(diary);
-    let double = |x| 2 * x;
-    println!("3 doubled: {}", apply_to_3(double));
+    narrate!("\nand with more than one type parameter:");
+    listing!(ignore; "fn some_function<T, U>(t: T, u: U) -> i32\n\twhere T: Display + Clone,\n\t\tU: Clone + Debug {{ ... }}");
+
+    narrate!("\nWith:");
+    show_and_run! { SOURCE;
+        let greeting = "hello";
+        let mut farewell = "goodbye".to_owned();
+        // capture greeting by reference and farewell by value
+        let diary = || {
+            output!("I said {}", greeting); // Requires Fn
+            farewell.push_str("!!!"); // Requires FnMut to be captured as mutable reference
+            output!("Then I screamed {}", farewell);
+            output!("Now I can sleep. Zzzz.");
+
+            mem::drop(farewell); // Manually calling mem::drop requires farewell to e captured by value
+        };
+        apply(diary);
+        let double = |x| 2 * x;
+    }
+    output!("3 doubled: {}", apply_to_3(double));
 }
 fn type_anonymity() {
-    helpers::section_subtitle(format!("Type Anonymity"));
-    println!("Closures necessarily require generics because they succinctly capture variables from enclosing scopes.");
-    println!("I understand what those words mean individually, but not in that order.");
+    narrate!("Closures necessarily require generics because they succinctly capture variables from enclosing scopes.");
+    narrate!("I understand what those words mean individually, but not in that order.");
 
-    println!("When a closure is defined the compiler creates an anonymous storage structure to store the captured variables");
-    println!("The compiler also implements the functionality via one of the traits: Fn, FnMut, or FnOnce");
-    println!("This type is assigned to the variable which is stored until calling.");
-    println!("Since the type is unknown, any usnage in a function requires generics");
-    println!("An unbounded tyoe parameter <T> would not allowed because it is ambiguous, but it can be bounded by on of the traits");
+    narrate!("When a closure is defined the compiler creates an anonymous storage structure to store the captured variables");
+    narrate!("The compiler also implements the functionality via one of the traits: Fn, FnMut, or FnOnce");
+    narrate!("This type is assigned to the variable which is stored until calling.");
+    narrate!("Since the type is unknown, any usnage in a function requires generics");
+    narrate!("An unbounded tyoe parameter <T> would not allowed because it is ambiguous, but it can be bounded by on of the traits");
 
     fn apply<F>(f: F)
     where
@@ -277,44 +278,42 @@ fn type_anonymity() {
         f();
     }
     let x = 7;
-    let print = || println!("{}", x);
+    let print = || output!("{}", x);
     apply(print);
 }
 
 fn input_functions() {
-    helpers::section_subtitle(format!("Input Functions (an aside from closures)"));
-    println!("Functions can be used as arguments as well as closures");
-    println!("If you define a function that takes a closure as a parameter, any functions that satisfies the closure's bound can be passed");
+    narrate!("Functions can be used as arguments as well as closures");
+    narrate!("If you define a function that takes a closure as a parameter, any functions that satisfies the closure's bound can be passed");
 
     fn call_me<F: Fn()>(f: F) {
         f()
     } // takes generic F argument bounded by Fn and calls it
     fn function() {
-        println!("I'm a function!");
+        output!("I'm a function!");
     }
-    let closure = || println!("I'm a closure!");
+    let closure = || output!("I'm a closure!");
     call_me(closure);
     call_me(function);
 }
 fn as_output_parameters() {
-    helpers::section_subtitle(format!("Closures as Output Parameters"));
-    println!("Since you can accept a closure you should be able to return a closure from a function, right?");
-    println!("You can, but you have to Box the return because you can't return a generic type");
-    println!("So by using Box you can make the closure concrete and return it");
-
-    println!("\nThe valid traits for returns are slightly different than before");
-    println!("\tFn - same as input");
-    println!("\tFnMut - same as input");
-    println!("\tFnOnce - requires FnBox, but that's unstable, so it can't be used (yet)");
-    println!("Additionally the move keyword must be used, which makes sense because otherwise the references would be dropped when the function exited, voiding the closure contents");
-
-    fn create_fn() -> Box<Fn()> {
+    narrate!("Since you can accept a closure you should be able to return a closure from a function, right?");
+    narrate!("You can, but you have to Box the return because you can't return a generic type");
+    narrate!("So by using Box you can make the closure concrete and return it");
+
+    narrate!("\nThe valid traits for returns are slightly different than before");
+    narrate!("\tFn - same as input");
+    narrate!("\tFnMut - same as input");
+    narrate!("\tFnOnce - requires FnBox, but that's unstable, so it can't be used (yet)");
+    narrate!("Additionally the move keyword must be used, which makes sense because otherwise the references would be dropped when the function exited, voiding the closure contents");
+
+    fn create_fn() -> Box<dyn Fn()> {
         let text = "Fn".to_owned();
-        Box::new(move || println!("This is a {}", text))
+        Box::new(move || output!("This is a {}", text))
     }
-    fn create_fnmut() -> Box<FnMut()> {
+    fn create_fnmut() -> Box<dyn FnMut()> {
         let text = "FnMut".to_owned();
-        Box::new(move || println!("This is a {}", text))
+        Box::new(move || output!("This is a {}", text))
     }
 
     let fn_plain = create_fn();
@@ -323,49 +322,49 @@ fn as_output_parameters() {
     fn_plain();
     fn_mut();
 }
+#[allow(clippy::useless_vec, clippy::manual_contains)]
 fn examples_in_std() {
-    helpers::section_subtitle(format!("Here are some examples from the std library:"));
 
-    println!("\n1. Iterator::any");
-    println!("Iterator::any is a function that when passed an iterator will return true if any element datisfies the prediate, otherwise false");
+    narrate!("\n1. Iterator::any");
+    narrate!("Iterator::any is a function that when passed an iterator will return true if any element datisfies the prediate, otherwise false");
     let vec1 = vec![1, 2, 3];
     let vec2 = vec![4, 5, 6];
-    println!("2 in vec1: {}", vec1.iter().any(|&x| x == 2));
-    println!("2 in vec2: {}", vec2.into_iter().any(|x| x == 2));
+    output!("2 in vec1: {}", vec1.iter().any(|&x| x == 2));
+    output!("2 in vec2: {}", vec2.into_iter().any(|x| x == 2));
 
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
-    println!("2 in array1: {}", array1.iter().any(|&x| x == 2));
-    println!("2 in array2: {}", array2.into_iter().any(|&x| x == 2));
+    output!("2 in array1: {}", array1.iter().any(|&x| x == 2));
+    output!("2 in array2: {}", array2.iter().any(|&x| x == 2));
 
-    println!("\n2. Iterator::find");
-    println!("Iterator::find is a function that when passed an iterator returns the first element that satisfies the predicate (as an Option)");
+    narrate!("\n2. Iterator::find");
+    narrate!("Iterator::find is a function that when passed an iterator returns the first element that satisfies the predicate (as an Option)");
     let vec1 = vec![1, 2, 3];
     let vec2 = vec![4, 5, 6];
     let mut iter = vec1.iter();
     let mut into_iter = vec2.into_iter();
-    println!("Find 2 in vec1: {:?}", iter.find(|&&x| x == 2));
-    println!("Find 2 in vec2: {:?}", into_iter.find(|&x| x == 2));
+    output!("Find 2 in vec1: {:?}", iter.find(|&&x| x == 2));
+    output!("Find 2 in vec2: {:?}", into_iter.find(|&x| x == 2));
 
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
-    println!("Find 2 in array1: {:?}", array1.iter().find(|&&x| x == 2));
-    println!(
+    output!("Find 2 in array1: {:?}", array1.iter().find(|&&x| x == 2));
+    output!(
         "Find 2 in array2: {:?}",
-        array2.into_iter().find(|&&x| x == 2)
+        array2.iter().find(|&&x| x == 2)
     );
 }
 
+#[allow(clippy::unnecessary_fold)]
 fn higher_order_functions() {
-    helpers::section_title(format!("Higher Order Functions"));
-    println!("HOFs are functions which take >0 functions and/or produce a more useful function");
-    println!("These are apparently what give Rust it's functional flavour");
-    println!("Option and Iterator have more than a few HOFs");
+    narrate!("HOFs are functions which take >0 functions and/or produce a more useful function");
+    narrate!("These are apparently what give Rust it's functional flavour");
+    narrate!("Option and Iterator have more than a few HOFs");
 
     fn is_odd(n: u32) -> bool {
         n % 2 == 1
     }
-    println!("Find the sum of all the squared odd numbers under 1000");
+    narrate!("Find the sum of all the squared odd numbers under 1000");
     let upper = 1000;
 
     // Imperatively
@@ -378,7 +377,7 @@ fn higher_order_functions() {
             acc += n_squared;
         }
     }
-    println!("Imperatively: {}", acc);
+    output!("Imperatively: {}", acc);
 
     // Functionally
     let sum_of_squared_odd_numbers: u32 = (0..)
@@ -386,38 +385,117 @@ fn higher_order_functions() {
         .take_while(|&n_squared| n_squared < upper) // below upper limit
         .filter(|&n_squared| is_odd(n_squared)) // that are odd
         .fold(0, |acc, n_squared| acc + n_squared); // sum them
-    println!("Functionally: {}", sum_of_squared_odd_numbers);
+    output!("Functionally: {}", sum_of_squared_odd_numbers);
 }
 
 fn diverging_functions() {
-    helpers::section_title(format!("Diverging functions"));
-    println!("Diverging functions are functions that never return");
-    println!("They are marked with !, which is an empty type");
-    println!("\tfn foo() -> ! {{\n\t\tpanic!(\"This call never returns.\");\n\t}}");
-    fn foo() -> ! {
-        panic!("This call never returns.");
+    narrate!("Diverging functions are functions that never return");
+    narrate!("They are marked with !, which is an empty type");
+    show_and_run! { SOURCE;
+        fn foo() -> ! {
+            panic!("This call never returns.");
+        }
     }
-    println!("! is event less of a thing than nothing, at least nothing will return ()");
-    println!("It's an abstract concept and the way it's explained makes me think it's sort of just background info");
+    narrate!("\nCalling foo() can only end in a panic:");
+    panics::show(|| foo());
+    narrate!("\n! is event less of a thing than nothing, at least nothing will return ()");
+    narrate!("It's an abstract concept and the way it's explained makes me think it's sort of just background info");
 }
 
-pub fn run() {
-    helpers::example_title(format!("Example 9: Functions"));
-    println!("Functions are declared using the `fn` keyword");
-    println!("Function arguments must by type annotated, just like variables");
-    println!("If the function returns a variable, the type must be specified after an arrow (->)");
-    println!("\nfn run() {{ ... }}");
-    println!("\nfn run(variable_1:u64, variable_2: &mut u32) -> bool {{ ... }}");
-    println!("It works the same as a function in JS/Python other than the type strictness");
+fn intro() {
+    narrate!("Functions are declared using the `fn` keyword");
+    narrate!("Function arguments must by type annotated, just like variables");
+    narrate!("If the function returns a variable, the type must be specified after an arrow (->)");
+    listing!(ignore; "fn run() {{ ... }}");
+    listing!(ignore; "fn run(variable_1:u64, variable_2: &mut u32) -> bool {{ ... }}");
+    narrate!("It works the same as a function in JS/Python other than the type strictness");
 
-    println!("\nFizzBuzz with functions:");
+    narrate!("\nFizzBuzz with functions:");
     fizzbuzz_to(30);
     // fizzbuzz_to(100);
     // fizzbuzz_to(1000);
     // fizzbuzz_to(10000);
     // fizzbuzz_to(1_000_000);
-    methods();
-    closures();
-    higher_order_functions();
-    diverging_functions();
+}
+
+#[allow(clippy::manual_contains)]
+fn quiz() -> Vec<Question> {
+    vec![
+        question!("{}", (0..).map(|n| n * n).take_while(|&n| n < 1000).filter(|&n| n % 2 == 1).sum::<u32>()),
+        question!("{}", [1, 2, 3].iter().any(|&x| x == 2)),
+        question!("{:?}", [4, 5, 6].iter().position(|&x| x == 5)),
+        question!("{:?}", vec![1, 2, 3].into_iter().find(|&x| x == 4)),
+    ]
+}
+
+pub struct Functions;
+
+const SECTIONS: &[Section] = &[
+    Section::new("methods", "Methods", methods),
+    Section::new("closures", "Closures", closures).with_subsections(&[
+        Section::new("capturing", "Capturing", capturing),
+        Section::new("as_input_parameters", "Closures as input parameters", as_input_parameters),
+        Section::new("type_anonymity", "Type Anonymity", type_anonymity),
+        Section::new("input_functions", "Input Functions (an aside from closures)", input_functions),
+        Section::new("as_output_parameters", "Closures as Output Parameters", as_output_parameters),
+        Section::new("examples_in_std", "Examples in std", examples_in_std),
+    ]),
+    Section::new("higher_order_functions", "Higher Order Functions", higher_order_functions),
+    Section::new("diverging_functions", "Diverging functions", diverging_functions),
+];
+
+const CONSUMED_TWICE: CompileFail = CompileFail {
+    slug: "consumed_twice",
+    context: "use std::mem;",
+    code: r#"
+let movable = Box::new(3);
+let consume = || {
+    println!("movable: {}", movable);
+    mem::drop(movable);
+};
+
+consume();
+consume();
+"#,
+    error: "E0382",
+};
+
+const MOVED_HAYSTACK: CompileFail = CompileFail {
+    slug: "moved_haystack",
+    context: "",
+    code: r#"
+let haystack = vec![1, 2, 3];
+let contains = move |needle| haystack.contains(needle);
+println!("haystack contains &1: {}", contains(&1));
+println!("haystack contains {} elements", haystack.len());
+"#,
+    error: "E0382",
+};
+
+const COMPILE_FAILS: &[CompileFail] = &[CONSUMED_TWICE, MOVED_HAYSTACK];
+
+impl Chapter for Functions {
+    fn slug(&self) -> &'static str {
+        "functions"
+    }
+
+    fn title(&self) -> &'static str {
+        "Functions"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn quiz(&self) -> Vec<Question> {
+        quiz()
+    }
+
+    fn compile_fails(&self) -> &'static [CompileFail] {
+        COMPILE_FAILS
+    }
 }
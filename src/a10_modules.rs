@@ -1,111 +1,163 @@
-use super::helpers;
-
-pub fn run() {
-    helpers::example_title(format!("Example 10: Modules"));
-    println!("Rust provides a powerful module system that can be used to hierarchically split code in logical units and manage visibility");
-    println!("A module is a colleciton of items: functions, structs, traits, impl blocks, and other modules");
-
-    module_visibility();
-    struct_visibility();
-    use_declaration();
-    super_and_self();
-    file_hierarchy();
+use super::chapter::{Chapter, CompileFail, Section};
+use super::diagnostics;
+use super::render::{listing, narrate, output, show_and_run};
+
+const SOURCE: &str = include_str!("a10_modules.rs");
+
+fn intro() {
+    narrate!("Rust provides a powerful module system that can be used to hierarchically split code in logical units and manage visibility");
+    narrate!("A module is a colleciton of items: functions, structs, traits, impl blocks, and other modules");
+}
+
+pub struct Modules;
+
+const SECTIONS: &[Section] = &[
+    Section::new("module_visibility", "Module Visibility", module_visibility),
+    Section::new("struct_visibility", "Struct Visibility", struct_visibility),
+    Section::new("use_declaration", "The `use` declaration", use_declaration),
+    Section::new("super_and_self", "`super` and `self`", super_and_self),
+    Section::new("file_hierarchy", "File Hierarchy", file_hierarchy),
+];
+
+const PUB_IN_PATH: CompileFail = CompileFail {
+    slug: "pub_in_path",
+    context: "
+mod visibility_module {
+    pub mod nested {
+        pub(in crate::visibility_module) fn public_function_in_my_mod() {}
+    }
+}",
+    code: "visibility_module::nested::public_function_in_my_mod();",
+    error: "E0603",
+};
+
+const COMPILE_FAILS: &[CompileFail] = &[PUB_IN_PATH];
+
+impl Chapter for Modules {
+    fn slug(&self) -> &'static str {
+        "modules"
+    }
+
+    fn title(&self) -> &'static str {
+        "Modules"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn compile_fails(&self) -> &'static [CompileFail] {
+        COMPILE_FAILS
+    }
 }
 
 mod visibility_module {
+    use crate::render::{output, output_inline};
+
     fn private_function() {
-        println!("called `visibility_module::private_function()`");
+        output!("called `visibility_module::private_function()`");
     }
 
     pub fn function() {
-        println!("called `visibility_module::function()`");
+        output!("called `visibility_module::function()`");
     }
 
     pub fn indirect_access() {
-        print!("called `visibility_module::indirect_access()`, that\n> ");
+        output_inline!("called `visibility_module::indirect_access()`, that\n> ");
         private_function();
     }
 
     pub mod nested {
+        use crate::render::{output, output_inline};
+
         pub fn function() {
-            println!("called `visibility_module::nested::function()`");
+            output!("called `visibility_module::nested::function()`");
         }
 
         #[allow(dead_code)]
         fn private_function() {
-            println!("called `visibility_module::nested::private_function()`");
+            output!("called `visibility_module::nested::private_function()`");
         }
 
         // Public
-        pub(in crate) fn public_function_in_my_mod() {
-            print!("called `visibility_module::nested::public_function_in_my_mod()`, that\n > ");
+        pub(in crate::a10_modules::visibility_module) fn public_function_in_my_mod() {
+            output_inline!("called `visibility_module::nested::public_function_in_my_mod()`, that\n > ");
             public_function_in_nested()
         }
 
         // Private
+        #[allow(clippy::needless_pub_self)]
         pub(self) fn public_function_in_nested() {
-            println!("called `visibility_module::nested::public_function_in_nested");
+            output!("called `visibility_module::nested::public_function_in_nested");
         }
 
         // only in Parent
         pub(super) fn public_function_in_super_mod() {
-            println!("called visibility_module::nested::public_function_in_super_mod");
+            output!("called visibility_module::nested::public_function_in_super_mod");
         }
     }
 
     pub fn call_public_function_in_my_mod() {
-        print!("called `visibility_module::call_public_funcion_in_my_mod()`, that\n> ");
+        output_inline!("called `visibility_module::call_public_funcion_in_my_mod()`, that\n> ");
         nested::public_function_in_my_mod();
-        print!("> ");
+        output_inline!("> ");
         nested::public_function_in_super_mod();
     }
 
     // only within the current crate
     pub(crate) fn public_function_in_crate() {
-        println!("called `visibility_module::public_function_in_crate()");
+        output!("called `visibility_module::public_function_in_crate()");
     }
 
     // Nested modules follow the same rules for visibility
     mod private_nested {
+        use crate::render::output;
+
         #[allow(dead_code)]
         pub fn function() {
-            println!("called `visibility_module::private_nested::function()`");
+            output!("called `visibility_module::private_nested::function()`");
         }
 
         // Private parent items will still restrict the visibility of a child item,
         // even if it is declared as visible within a bigger scope.
         #[allow(dead_code)]
         pub(crate) fn restricted_function() {
-            println!("called `visibility_module::private_nested::restricted_function()`");
+            output!("called `visibility_module::private_nested::restricted_function()`");
         }
     }
 }
 
 fn visibility_function() {
-    println!("called `visibility_function()`");
+    output!("called `visibility_function()`");
 }
 
 fn module_visibility() {
-    helpers::section_title(format!("Module Visibility"));
-    println!("Modules allow disambiguation between items that have the same name.");
-    println!("They can be private or public, and have private or public members");
-    println!("Public items, including those inside nested modules, can be accessed from outside the parent module.");
-    println!("Modules can be nested and they follow the same visibility rules\n");
-    println!(
+    narrate!("Modules allow disambiguation between items that have the same name.");
+    narrate!("They can be private or public, and have private or public members");
+    narrate!("Public items, including those inside nested modules, can be accessed from outside the parent module.");
+    narrate!("Modules can be nested and they follow the same visibility rules\n");
+    narrate!(
         "Private items of a module cannot be directly accessed, even if nested in a public module:"
     );
 
-    visibility_function();
-    visibility_module::function();
-    visibility_module::indirect_access();
-    visibility_module::nested::function();
-    visibility_module::call_public_function_in_my_mod();
+    show_and_run! { SOURCE;
+        visibility_function();
+        visibility_module::function();
+        visibility_module::indirect_access();
+        visibility_module::nested::function();
+        visibility_module::call_public_function_in_my_mod();
 
-    // pub(crate) items can be called from anywhere in the same crate
-    visibility_module::public_function_in_crate();
+        // pub(crate) items can be called from anywhere in the same crate
+        visibility_module::public_function_in_crate();
+    }
 
-    // pub(in path) items can only be called from within the mode specified
-    //visibility_module::nested::public_function_in_my_mod();
+    narrate!("\npub(in path) items can only be called from within the module specified,");
+    narrate!("so public_function_in_my_mod(), which is pub(in visibility_module), can't be called from here:");
+    diagnostics::show(&PUB_IN_PATH);
 }
 
 mod struct_module {
@@ -119,69 +171,79 @@ mod struct_module {
 
     impl<T> ClosedBox<T> {
         pub fn new(contents: T) -> ClosedBox<T> {
-            ClosedBox { contents: contents }
+            ClosedBox { contents }
         }
     }
 }
 
 fn struct_visibility() {
-    helpers::section_title(format!("Struct Visibility"));
-    println!("Structs can also be private or public");
-    println!("This privacy is maintained in the goal of encapsulation & only applies outside the mod the struct was defined in");
-    println!(
+    narrate!("Structs can also be private or public");
+    narrate!("This privacy is maintained in the goal of encapsulation & only applies outside the mod the struct was defined in");
+    narrate!(
         "This much works as you'd expect: public things are public, private things are private"
     );
 
-    // normal: pub/pub
-    let open_box = struct_module::OpenBox {
-        contents: "public information",
-    };
-    println!("The open box contains: {}", open_box.contents);
+    show_and_run! { SOURCE;
+        // normal: pub/pub
+        let open_box = struct_module::OpenBox {
+            contents: "public information",
+        };
+        output!("The open box contains: {}", open_box.contents);
 
-    // error: pub/priv
-    // let closed_box = struct_module::ClosedBox { contents: "classified information" };
+        // error: pub/priv
+        // let closed_box = struct_module::ClosedBox { contents: "classified information" };
 
-    // normal: pub/pub, allows access to private fields
-    let _closed_box = struct_module::ClosedBox::new("classified information");
+        // normal: pub/pub, allows access to private fields
+        let _closed_box = struct_module::ClosedBox::new("classified information");
 
-    // error: pub/priv
-    //println!("The closed box contains: {}", _closed_box.contents);
-    // TODO ^ Try uncommenting this line
+        // error: pub/priv
+        //println!("The closed box contains: {}", _closed_box.contents);
+        // TODO ^ Try uncommenting this line
+    }
 }
 
 fn use_declaration() {
-    helpers::section_title(format!("The `use` declaration"));
-    println!("Similar to Python's `import ... from ... as ...`,\nand especially like JS's destructured `const {{ x1, x2:y }} = import('module-name');`");
-    println!(
+    narrate!("Similar to Python's `import ... from ... as ...`,\nand especially like JS's destructured `const {{ x1, x2:y }} = import('module-name');`");
+    narrate!(
         "The `use` declaration is used to bind a path to a new name, usually for easier access"
     );
-    println!("It looks like this:");
-    println!("\tuse crate::deeply::nested::{{ some_fn, ATraitType, and_an_enum }}");
-    println!("\tuse crate::deeply::nested::some_fn as deep_nest_fn");
-    println!("\tuse crate::deeply::nested::some_fn");
+    narrate!("For a module like:");
+    listing!("mod deeply {{\n\tpub mod nested {{\n\t\tpub fn some_fn() {{}}\n\t\tpub trait ATraitType {{}}\n\t\tpub enum and_an_enum {{}}\n\t}}\n}}");
+    narrate!("It looks like this:");
+    listing!("use crate::deeply::nested::some_fn;");
+    narrate!("Several items from one path can be brought in at once:");
+    listing!("use crate::deeply::nested::{{ ATraitType, and_an_enum }};");
+    narrate!("And an item can be bound to a different name:");
+    listing!("use crate::deeply::nested::some_fn as deep_nest_fn;");
 }
 
 fn super_function() {
-    println!("called `super_function()`");
+    output!("called `super_function()`");
 }
 
 mod super_cool {
+    use crate::render::output;
+
     pub fn function() {
-        println!("called `super_cool::function()`");
+        output!("called `super_cool::function()`");
     }
 }
 
 mod super_module {
+    use crate::render::{output, output_inline};
+
     fn function() {
-        println!("called `super_module::function()`");
+        output!("called `super_module::function()`");
     }
     mod cool {
+        use crate::render::output;
+
         pub fn function() {
-            println!("called `super_module::cool::function()`");
+            output!("called `super_module::cool::function()`");
         }
     }
     pub fn indirect_call() {
-        print!("called `super_module::indirect_call()`, that\n> ");
+        output_inline!("called `super_module::indirect_call()`, that\n> ");
         // The `self` keyword refers to the current module scope - in this case `super_module`.
         // Calling `self::function()` and calling `function()` directly both give
         self::function();
@@ -200,24 +262,22 @@ mod super_module {
 }
 
 fn super_and_self() {
-    helpers::section_title(format!("`super` and `self`"));
-    println!("The `super` and `self` keywords can be used in the path to remove ambiguity and prevent unnecessary hardcoding of paths");
+    narrate!("The `super` and `self` keywords can be used in the path to remove ambiguity and prevent unnecessary hardcoding of paths");
 
     super_module::indirect_call();
 }
 
 use super::a10_modules_as_files as my_dir;
 fn file_hierarchy() {
-    helpers::section_subtitle(format!("File Hierarchy"));
-    println!("Modules can be mapped to a file/dir structure");
+    narrate!("Modules can be mapped to a file/dir structure");
 
     fn function() {
-        println!("Called function()");
+        output!("Called function()");
     }
     my_dir::function();
     function();
     my_dir::indirect_access();
     my_dir::nested::function();
 
-    println!("\nThis is the same as the super example, but in different files.");
+    narrate!("\nThis is the same as the super example, but in different files.");
 }
@@ -1,4 +1,6 @@
+use crate::render::output;
+
 #[allow(dead_code)]
 pub fn public_function() {
-    println!("called `my_dir::inaccessible::public_function()`");
+    output!("called `my_dir::inaccessible::public_function()`");
 }
\ No newline at end of file
@@ -1,16 +1,18 @@
+use crate::render::{output, output_inline};
+
 mod inaccessible;
 pub mod nested;
 
 pub fn function() {
-    println!("called `my_dir::function()`");
+    output!("called `my_dir::function()`");
 }
 
 fn private_function() {
-    println!("called `my_dir::private_function()`");
+    output!("called `my_dir::private_function()`");
 }
 
 pub fn indirect_access() {
-    print!("called `my_dir::indirect_access()`, that\n> ");
+    output_inline!("called `my_dir::indirect_access()`, that\n> ");
 
     private_function();
 }
\ No newline at end of file
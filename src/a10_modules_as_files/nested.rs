@@ -1,8 +1,10 @@
+use crate::render::output;
+
 pub fn function() {
-    println!("called `my_dir::nested::function()`");
+    output!("called `my_dir::nested::function()`");
 }
 
 #[allow(dead_code)]
 fn private_function() {
-    println!("called `my_dir::nested::private_function()`");
+    output!("called `my_dir::nested::private_function()`");
 }
\ No newline at end of file
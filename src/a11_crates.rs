@@ -1,27 +1,68 @@
-use super::helpers;
+use super::chapter::{Chapter, CompileFail, Section};
+use super::diagnostics;
+use super::render::{narrate, output, show_and_run};
 
-pub fn run () {
-    helpers::example_title(format!("Example 11: Crates"));
-    println!("A crate is a compilation unit in Rust.");
-    println!("Along the same lines as Python libraries and JavaScript packages.");
+const SOURCE: &str = include_str!("a11_crates.rs");
 
-    println!("\nWhen a rust program is compiled by `rustc`, the base file given to the compiler is treated as the crate file");
-    println!("If that file has `mod` declarations in it, the module contents will be placed where the declarations are, before compilation");
-    println!("This means that modules are never compiled on their own, only as complete crates");
+fn intro() {
+    narrate!("A crate is a compilation unit in Rust.");
+    narrate!("Along the same lines as Python libraries and JavaScript packages.");
 
-    println!("\nA crate can be compiled into a binary [default] or a library (exe or dll for anyone older than me)");
+    narrate!("\nWhen a rust program is compiled by `rustc`, the base file given to the compiler is treated as the crate file");
+    narrate!("If that file has `mod` declarations in it, the module contents will be placed where the declarations are, before compilation");
+    narrate!("This means that modules are never compiled on their own, only as complete crates");
 
-    libraries();
+    narrate!("\nA crate can be compiled into a binary [default] or a library (exe or dll for anyone older than me)");
+}
+
+pub struct Crates;
+
+const SECTIONS: &[Section] = &[
+    Section::new("libraries", "Crate as Library and `extern crate`", libraries),
+];
+
+// The library is compiled in as a module, which has the same privacy rules as a crate
+const PRIVATE_FUNCTION: CompileFail = CompileFail {
+    slug: "private_function",
+    context: concat!("mod rary {\n", include_str!("../rary/src/lib.rs"), "}\n"),
+    code: "rary::private_function();",
+    error: "E0603",
+};
+
+const COMPILE_FAILS: &[CompileFail] = &[PRIVATE_FUNCTION];
+
+impl Chapter for Crates {
+    fn slug(&self) -> &'static str {
+        "crates"
+    }
+
+    fn title(&self) -> &'static str {
+        "Crates"
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        SECTIONS
+    }
+
+    fn intro(&self) {
+        intro();
+    }
+
+    fn compile_fails(&self) -> &'static [CompileFail] {
+        COMPILE_FAILS
+    }
 }
 
 fn libraries () {
-    helpers::section_title(format!("Crate as Library and `extern crate`"));
-    println!("Liraries are declared in Cargo.toml and autoimported into the global scope");
-    println!("\nThe same visibility rules that apply to modules apply to libraries");
+    narrate!("Liraries are declared in Cargo.toml and autoimported into the global scope");
+    narrate!("\nThe same visibility rules that apply to modules apply to libraries");
 
-    rary::public_function();
-    // rary::private_function(); // private
-    rary::indirect_access();
+    show_and_run! { SOURCE;
+        output!("{}", rary::public_function());
+        output!("{}", rary::indirect_access());
+    }
+    narrate!("\nBut its private functions can't be called from outside it:");
+    diagnostics::show(&PRIVATE_FUNCTION);
 
-    println!("\nNote: Rust by Example is a bit out of date here, but the learning was useful");
+    narrate!("\nNote: Rust by Example is a bit out of date here, but the learning was useful");
 }
\ No newline at end of file
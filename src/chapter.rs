@@ -0,0 +1,202 @@
+use super::panics;
+use super::render::{self, Level};
+use super::{
+    a01_hello_world, a02_primitives, a03_custom_types, a04_variable_bindings, a05_types,
+    a06_conversion, a07_expressions, a08_flow_control, a09_functions, a10_modules, a11_crates,
+    a12_cargo,
+};
+
+// Every chapter in course order - a chapter's number is its position in this list
+pub const CHAPTERS: [&dyn Chapter; 12] = [
+    &a01_hello_world::HelloWorld,
+    &a02_primitives::Primitives,
+    &a03_custom_types::CustomTypes,
+    &a04_variable_bindings::VariableBindings,
+    &a05_types::Types,
+    &a06_conversion::Conversion,
+    &a07_expressions::Expressions,
+    &a08_flow_control::FlowControl,
+    &a09_functions::Functions,
+    &a10_modules::Modules,
+    &a11_crates::Crates,
+    &a12_cargo::Cargo,
+];
+
+// A section along with how deeply it's nested in its chapter, 0 for top-level sections
+pub type OutlineEntry = (usize, &'static Section);
+
+pub struct Section {
+    pub slug: &'static str,
+    pub title: &'static str,
+    pub run: fn(),
+    pub subsections: &'static [Section],
+}
+
+impl Section {
+    pub const fn new(slug: &'static str, title: &'static str, run: fn()) -> Section {
+        Section {
+            slug,
+            title,
+            run,
+            subsections: &[],
+        }
+    }
+
+    pub const fn with_subsections(self, subsections: &'static [Section]) -> Section {
+        Section {
+            subsections,
+            ..self
+        }
+    }
+
+    // Print the title and body of just this section, top-level sections get the bigger title.
+    // A panic in the body is shown and stops just this section.
+    pub fn show(&self, depth: usize) {
+        if depth == 0 {
+            render::title(Level::Section, self.title);
+        } else {
+            render::title(Level::Subsection, self.title);
+        }
+        panics::run_section(self.slug, self.run);
+    }
+
+    // Show this section followed by all of its subsections
+    pub fn run(&self, depth: usize) {
+        self.show(depth);
+        for subsection in self.subsections {
+            subsection.run(depth + 1);
+        }
+    }
+
+    // This section and all of its subsections in reading order
+    pub fn outline(&'static self, depth: usize) -> Vec<OutlineEntry> {
+        let mut sections = vec![(depth, self)];
+        for subsection in self.subsections {
+            sections.extend(subsection.outline(depth + 1));
+        }
+        sections
+    }
+}
+
+// A "what does this print?" question. The answer is worked out by really running the code,
+// so it can't disagree with what Rust does.
+pub struct Question {
+    pub code: &'static str,
+    pub answer: String,
+}
+
+// e.g. question!("{}", 1000 as u8) asks what `println!("{}", 1000 as u8);` prints
+macro_rules! question {
+    ($format:literal $(, $value:expr)* $(,)?) => {
+        $crate::chapter::Question {
+            code: concat!("println!(", stringify!($format $(, $value)*), ");"),
+            answer: format!($format $(, $value)*),
+        }
+    };
+}
+pub(crate) use question;
+
+// A small program for the reader to finish. Whatever their version prints is compared with
+// `expected`, which runs the chapter's own code on the same input.
+pub struct Exercise {
+    pub slug: &'static str,
+    pub title: &'static str,
+    // The program to start from, with `todo!()` where the reader's code goes
+    pub stub: &'static str,
+    pub expected: fn() -> String,
+}
+
+// Code that's meant not to compile, shown with the error the installed rustc really gives for it.
+// It's compiled as `context`, then `code` inside main().
+pub struct CompileFail {
+    pub slug: &'static str,
+    // Anything `code` needs that isn't worth showing, e.g. the module it calls into
+    pub context: &'static str,
+    pub code: &'static str,
+    // The error code it has to fail with, e.g. "E0603"
+    pub error: &'static str,
+}
+
+pub trait Chapter {
+    // Used to pick the chapter on the command line, e.g. `custom-types`
+    fn slug(&self) -> &'static str;
+    fn title(&self) -> &'static str;
+    fn sections(&self) -> &'static [Section];
+    // Whatever the chapter prints before its first section
+    fn intro(&self) {}
+    fn quiz(&self) -> Vec<Question> {
+        Vec::new()
+    }
+    fn exercises(&self) -> &'static [Exercise] {
+        &[]
+    }
+    fn compile_fails(&self) -> &'static [CompileFail] {
+        &[]
+    }
+
+    fn number(&self) -> usize {
+        CHAPTERS
+            .iter()
+            .position(|chapter| chapter.slug() == self.slug())
+            .map_or(0, |index| index + 1)
+    }
+
+    fn show_intro(&self) {
+        render::title(
+            Level::Example,
+            &format!("Example {}: {}", self.number(), self.title()),
+        );
+        self.intro();
+    }
+
+    fn run(&self) {
+        self.show_intro();
+        for section in self.sections() {
+            section.run(0);
+        }
+    }
+
+    // Every section and subsection in reading order
+    fn outline(&self) -> Vec<OutlineEntry> {
+        self.sections()
+            .iter()
+            .flat_map(|section| section.outline(0))
+            .collect()
+    }
+
+    fn find_section(&self, slug: &str) -> Option<OutlineEntry> {
+        self.outline()
+            .into_iter()
+            .find(|(_, section)| section.slug == slug)
+    }
+}
+
+// Find a chapter by its number (`3`) or its slug (`custom-types`)
+pub fn find(name: &str) -> Option<&'static dyn Chapter> {
+    if let Ok(number) = name.parse::<usize>() {
+        return number
+            .checked_sub(1)
+            .and_then(|index| CHAPTERS.get(index))
+            .copied();
+    }
+    CHAPTERS.iter().find(|chapter| chapter.slug() == name).copied()
+}
+
+// Find a chapter and, if one is named after a `/`, a section inside it, e.g. `8/match_guards`
+pub fn lookup(name: &str) -> Result<(&'static dyn Chapter, Option<OutlineEntry>), String> {
+    let (chapter_name, section_name) = match name.find('/') {
+        Some(index) => (&name[..index], Some(&name[index + 1..])),
+        None => (name, None),
+    };
+    let chapter =
+        find(chapter_name).ok_or_else(|| format!("No chapter called `{}`", chapter_name))?;
+    match section_name {
+        Some(section_name) => {
+            let section = chapter.find_section(section_name).ok_or_else(|| {
+                format!("Chapter {} has no section called `{}`", chapter.number(), section_name)
+            })?;
+            Ok((chapter, Some(section)))
+        }
+        None => Ok((chapter, None)),
+    }
+}
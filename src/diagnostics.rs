@@ -0,0 +1,53 @@
+use super::chapter::CompileFail;
+use super::render::{self, note, output, Code};
+use super::rustc;
+use super::source;
+use std::fs;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Compiling takes a moment and a scratch directory, so chapters only ask rustc for its errors when
+// --compile-errors says to, and otherwise just give the error code each case is checked to fail with
+static COMPILE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_compile(compile: bool) {
+    COMPILE.store(compile, Ordering::Relaxed);
+}
+
+pub fn compiling() -> bool {
+    COMPILE.load(Ordering::Relaxed)
+}
+
+fn program(case: &CompileFail) -> String {
+    format!("{}\nfn main() {{\n{}\n}}\n", case.context, case.code)
+}
+
+// Compile the case and return rustc's first error, e.g.
+// "error[E0603]: function `private_function` is private", or None if it compiled after all
+pub fn first_error(case: &CompileFail) -> io::Result<Option<String>> {
+    let dir = rustc::ScratchDir::new(&format!("compile-fail-{}", case.slug))?;
+    let source = dir.join(format!("{}.rs", case.slug));
+    fs::write(&source, program(case))?;
+    let output = rustc::check(&source, &dir)?;
+    if output.status.success() {
+        return Ok(None);
+    }
+    let errors = String::from_utf8_lossy(&output.stderr);
+    let error = errors.lines().find(|line| line.starts_with("error"));
+    Ok(Some(error.unwrap_or(errors.trim()).to_string()))
+}
+
+// List the case's code, then what the compiler says about it
+pub fn show(case: &CompileFail) {
+    render::write_listing(Code::CompileFail, &source::dedent(case.code));
+    if !compiling() {
+        output!("error[{}]", case.error);
+        note!("(Run with --compile-errors to see what rustc says)");
+        return;
+    }
+    match first_error(case) {
+        Ok(Some(error)) => output!("{}", error),
+        Ok(None) => note!("(This is meant not to compile, but it did)"),
+        Err(error) => note!("(The compiler's error can't be shown: {})", error),
+    }
+}
@@ -0,0 +1,186 @@
+use super::chapter::{self, Chapter, Exercise, CHAPTERS};
+use super::rustc;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Long enough for anything the exercises need, short enough that an accidental infinite loop
+// doesn't look like the checker has hung
+const TIME_LIMIT: Duration = Duration::from_secs(5);
+
+// The most expected lines times printed lines that get a full diff. The table behind it grows
+// with both, so past this only the first difference is shown. The exercises print a few dozen
+// lines at most, so a real attempt is always well under it.
+const DIFF_LIMIT: usize = 10_000;
+// How much of a diff is printed before the rest is just counted
+const DIFF_LINES: usize = 50;
+
+// Find an exercise by `<chapter>/<exercise>`, e.g. `2/transpose`
+pub fn lookup(name: &str) -> Result<(&'static dyn Chapter, &'static Exercise), String> {
+    let (chapter_name, exercise_name) = match name.find('/') {
+        Some(index) => (&name[..index], &name[index + 1..]),
+        None => return Err(format!("Exercises are named `<chapter>/<exercise>`, not `{}`", name)),
+    };
+    let chapter =
+        chapter::find(chapter_name).ok_or_else(|| format!("No chapter called `{}`", chapter_name))?;
+    let exercise = chapter
+        .exercises()
+        .iter()
+        .find(|exercise| exercise.slug == exercise_name)
+        .ok_or_else(|| {
+            format!("Chapter {} has no exercise called `{}`", chapter.number(), exercise_name)
+        })?;
+    Ok((chapter, exercise))
+}
+
+// Where the reader's answer lives if they don't say, e.g. `transpose.rs` in the current directory
+pub fn default_path(exercise: &Exercise) -> PathBuf {
+    PathBuf::from(format!("{}.rs", exercise.slug))
+}
+
+pub fn list() {
+    for &chapter in CHAPTERS.iter() {
+        for exercise in chapter.exercises() {
+            println!(
+                "{:<24} {} (Example {}: {})",
+                format!("{}/{}", chapter.number(), exercise.slug),
+                exercise.title,
+                chapter.number(),
+                chapter.title()
+            );
+        }
+    }
+}
+
+// Write out the stub to work on, refusing to overwrite an answer that's already been started
+pub fn start(exercise: &Exercise, path: &Path) -> io::Result<()> {
+    if path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists, so it's been left alone", path.display()),
+        ));
+    }
+    fs::write(path, exercise.stub)?;
+    println!("Wrote {} - fill in the `todo!()`s, then check it", path.display());
+    Ok(())
+}
+
+// Line by line differences between what was expected and what was printed, using the longest
+// common subsequence of lines. Lines in both start with ` `, missing lines with `-`, and
+// lines that shouldn't be there with `+`.
+fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    if expected.len().saturating_mul(actual.len()) > DIFF_LIMIT {
+        return first_difference(&expected, &actual);
+    }
+
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(format!("  {}", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+// The same markers as `diff`, but only up to the first line that differs, with a couple of the
+// matching lines before it
+fn first_difference(expected: &[&str], actual: &[&str]) -> Vec<String> {
+    let same = expected.iter().zip(actual).take_while(|(expected, actual)| expected == actual).count();
+    let mut lines: Vec<String> =
+        expected[same.saturating_sub(2)..same].iter().map(|line| format!("  {}", line)).collect();
+    if let Some(line) = expected.get(same) {
+        lines.push(format!("- {}", line));
+    }
+    if let Some(line) = actual.get(same) {
+        lines.push(format!("+ {}", line));
+    }
+    lines.push(format!(
+        "(That's the first difference, at line {}. It printed {} lines where {} were expected, \
+        too many to compare the rest.)",
+        same + 1,
+        actual.len(),
+        expected.len()
+    ));
+    lines
+}
+
+// Compile the reader's answer, run it and compare what it prints with the chapter's own code.
+// Returns whether it was right; anything that stops the check happening at all is an error.
+pub fn check(chapter: &dyn Chapter, exercise: &Exercise, path: &Path) -> io::Result<bool> {
+    let source = fs::canonicalize(path).map_err(|error| {
+        io::Error::new(error.kind(), format!("couldn't read {}: {}", path.display(), error))
+    })?;
+    let dir = rustc::ScratchDir::new(exercise.slug)?;
+    let binary = dir.join(exercise.slug);
+
+    println!("Compiling {}...", path.display());
+    let compiled = rustc::compile(&source, &binary)?;
+    if !compiled.status.success() {
+        println!("It doesn't compile yet:\n");
+        print!("{}", String::from_utf8_lossy(&compiled.stderr));
+        return Ok(false);
+    }
+
+    let run = rustc::run(&binary, TIME_LIMIT)?;
+    let expected = (exercise.expected)();
+    if run.truncated {
+        println!(
+            "It printed more than {} KiB, so only the start of it was kept",
+            rustc::OUTPUT_LIMIT / 1024
+        );
+    }
+
+    match run.status {
+        None => println!(
+            "It was still running after {} seconds, so it was stopped",
+            TIME_LIMIT.as_secs()
+        ),
+        Some(status) if !status.success() => {
+            println!("It stopped with {}:\n", status);
+            print!("{}", run.stderr);
+        }
+        Some(_) if !run.truncated && run.stdout.lines().eq(expected.lines()) => {
+            println!(
+                "Correct! That's exactly what Example {}: {} prints.",
+                chapter.number(),
+                chapter.title()
+            );
+            return Ok(true);
+        }
+        Some(_) => {}
+    }
+
+    println!("\nWhat it printed doesn't match yet (- expected, + yours):");
+    let lines = diff(&expected, &run.stdout);
+    for line in lines.iter().take(DIFF_LINES) {
+        println!("{}", line);
+    }
+    if lines.len() > DIFF_LINES {
+        println!("(and {} more lines of differences)", lines.len() - DIFF_LINES);
+    }
+    Ok(false)
+}
@@ -1,19 +1,145 @@
-pub fn example_title (title: String) {
-    let length = title.len() + 4;
-    println!("\n\t/{:*<1$}\\", "", length);
-    println!("\t|{: ^1$}|", title, length);
-    println!("\t\\{:*<1$}/\n", "", length);
+use super::render::Theme;
+use std::env;
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+// Box art for titles in the plain and ansi formats
+
+// What a title's box is drawn with: the top left corner, the top and bottom, the top right
+// corner, the sides, then the bottom left and bottom right corners
+type Frame = [char; 6];
+
+const ASCII_EXAMPLE: Frame = ['/', '*', '\\', '|', '\\', '/'];
+const ASCII_SECTION: Frame = ['/', '-', '\\', '|', '\\', '/'];
+const UNICODE_EXAMPLE: Frame = ['╔', '═', '╗', '║', '╚', '╝'];
+const UNICODE_SECTION: Frame = ['┌', '─', '┐', '│', '└', '┘'];
+
+fn boxed(out: &mut dyn Write, frame: Frame, title: &str) -> io::Result<()> {
+    let [top_left, line, top_right, side, bottom_left, bottom_right] = frame;
+    let length = width(title) + 4;
+    let line = line.to_string().repeat(length);
+    writeln!(out, "\n\t{}{}{}", top_left, line, top_right)?;
+    writeln!(out, "\t{}{}{}", side, centre(title, length), side)?;
+    writeln!(out, "\t{}{}{}", bottom_left, line, bottom_right)
+}
+
+// The minimal theme has no art at all, so a screen reader reads out just the title
+fn bare(out: &mut dyn Write, title: &str) -> io::Result<()> {
+    writeln!(out, "\n{}", title)
 }
 
-pub fn section_title (title: String) {
-    let length = title.len() + 4;
-    println!("\n\t/{:-<1$}\\", "", length);
-    println!("\t|{: ^1$}|", title, length);
-    println!("\t\\{:-<1$}/\n", "", length);
+pub fn example_title (out: &mut dyn Write, theme: Theme, title: &str) -> io::Result<()> {
+    match theme {
+        Theme::Ascii | Theme::HighContrast => boxed(out, ASCII_EXAMPLE, title),
+        Theme::Unicode => boxed(out, UNICODE_EXAMPLE, title),
+        Theme::Minimal => bare(out, title),
+    }
 }
 
-pub fn section_subtitle (title: String) {
-    let length = title.len();
-    println!("\n\t{: ^1$}", title, length);
-    println!("\t{:=<1$}\n", "", length);
-}
\ No newline at end of file
+pub fn section_title (out: &mut dyn Write, theme: Theme, title: &str) -> io::Result<()> {
+    match theme {
+        Theme::Ascii | Theme::HighContrast => boxed(out, ASCII_SECTION, title),
+        Theme::Unicode => boxed(out, UNICODE_SECTION, title),
+        Theme::Minimal => bare(out, title),
+    }
+}
+
+pub fn section_subtitle (out: &mut dyn Write, theme: Theme, title: &str) -> io::Result<()> {
+    let underline = match theme {
+        Theme::Ascii | Theme::HighContrast => "=",
+        Theme::Unicode => "─",
+        Theme::Minimal => return bare(out, title),
+    };
+    writeln!(out, "\n\t{}", title)?;
+    writeln!(out, "\t{}", underline.repeat(width(title)))
+}
+
+// How many columns a character takes up on a terminal: none for control characters and ones
+// that combine with the character before, two for wide ones like CJK and most emoji
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0..=0x1f | 0x7f..=0x9f => 0,
+        0x300..=0x36f | 0x200b..=0x200f | 0x20d0..=0x20ff | 0xfe00..=0xfe0f | 0xfe20..=0xfe2f => 0,
+        0x1100..=0x115f
+        | 0x2e80..=0x303e
+        | 0x3041..=0x33ff
+        | 0x3400..=0x4dbf
+        | 0x4e00..=0x9fff
+        | 0xa000..=0xa4cf
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe30..=0xfe4f
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x1f300..=0x1f64f
+        | 0x1f900..=0x1f9ff
+        | 0x20000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
+// How many columns `text` takes up, which isn't its length in bytes once it's past ASCII,
+// e.g. the ├── in a directory tree is 9 bytes but 3 columns
+pub fn width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+// `text` in the middle of `length` columns, any odd space going on the right like {:^}
+pub fn centre(text: &str, length: usize) -> String {
+    let space = length.saturating_sub(width(text));
+    format!("{:left$}{}{:right$}", "", text, "", left = space / 2, right = space - space / 2)
+}
+
+// How wide the terminal is, or None if stdout isn't a terminal, so output that's piped or saved
+// is never wrapped. $COLUMNS is used if it's set, otherwise `stty` is asked.
+pub fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()) {
+        return Some(columns).filter(|&columns| columns > 0);
+    }
+    // stty reports on its stdin, which mightn't be the terminal if input is piped in
+    let tty = File::open("/dev/tty").map_or_else(|_| Stdio::inherit(), Stdio::from);
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let size = String::from_utf8(output.stdout).ok()?;
+    size.split_whitespace().nth(1)?.parse().ok()
+}
+
+// How many columns the whitespace a line starts with takes up, with tab stops every 8 columns
+fn indent_width(indent: &str) -> usize {
+    indent.chars().fold(0, |column, c| match c {
+        '\t' => column / 8 * 8 + 8,
+        _ => column + 1,
+    })
+}
+
+// Wrap each line of `text` at spaces so it fits in `columns`. The lines a line is wrapped onto
+// start with the same tabs and spaces it did, so indented text stays indented.
+// A word too long for a line is left to overflow rather than being split.
+pub fn wrap(text: &str, columns: usize) -> String {
+    let mut wrapped = Vec::new();
+    for line in text.split('\n') {
+        let words = line.trim_start_matches(['\t', ' ']);
+        let indent = &line[..line.len() - words.len()];
+        let room = columns.saturating_sub(indent_width(indent));
+        let mut current = String::new();
+        for (index, word) in words.split(' ').enumerate() {
+            if index > 0 && !current.is_empty() && width(&current) + 1 + width(word) > room {
+                wrapped.push(format!("{}{}", indent, current));
+                current.clear();
+            } else if index > 0 {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        wrapped.push(format!("{}{}", indent, current));
+    }
+    wrapped.join("\n")
+}
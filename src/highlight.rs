@@ -0,0 +1,130 @@
+use super::render::escape_html;
+use super::source;
+
+// Splitting Rust listings into classes of token, for the ansi format to color and for exporters
+// to mark up. It only has to be good enough to highlight code that's shown, not to parse it.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Class {
+    // Names, punctuation and whitespace
+    Plain,
+    Keyword,
+    // Numbers, `true` and `false`
+    Literal,
+    // Strings and chars, including raw and byte strings
+    String,
+    Lifetime,
+    // A macro's name and its `!`, e.g. `println!`
+    Macro,
+    Comment,
+}
+
+impl Class {
+    // e.g. for a CSS class
+    pub fn name(self) -> &'static str {
+        match self {
+            Class::Plain => "plain",
+            Class::Keyword => "keyword",
+            Class::Literal => "literal",
+            Class::String => "string",
+            Class::Lifetime => "lifetime",
+            Class::Macro => "macro",
+            Class::Comment => "comment",
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+];
+
+fn is_identifier(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Where the identifier (or number) starting at `start` ends
+fn identifier_end(code: &str, start: usize) -> usize {
+    code[start..]
+        .find(|c: char| !is_identifier(c))
+        .map_or(code.len(), |length| start + length)
+}
+
+// A number's digits, suffix and any decimal point, but not the `..` of a range like 0..5
+fn number_end(code: &str, start: usize) -> usize {
+    let mut end = identifier_end(code, start);
+    let bytes = code.as_bytes();
+    while bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end = identifier_end(code, end + 1);
+    }
+    end
+}
+
+// A string, char or comment starting at `start`, as its class and where it ends
+fn literal(code: &str, start: usize) -> Option<(Class, usize)> {
+    let bytes = code.as_bytes();
+    // Byte strings and chars are lexed from after the b
+    let at = match (bytes[start], bytes.get(start + 1)) {
+        (b'b', Some(b'"' | b'\'' | b'r')) => start + 1,
+        _ => start,
+    };
+    let end = source::skip_literal(code, at).min(code.len());
+    if end <= at + 1 {
+        return None;
+    }
+    match bytes[at] {
+        // A line comment's newline isn't part of it
+        b'/' if bytes[at + 1] == b'/' && bytes[end - 1] == b'\n' => Some((Class::Comment, end - 1)),
+        b'/' => Some((Class::Comment, end)),
+        _ => Some((Class::String, end)),
+    }
+}
+
+// Split `code` into tokens, which between them are all of `code` in order
+pub fn tokens(code: &str) -> Vec<(Class, &str)> {
+    let mut tokens: Vec<(Class, usize, usize)> = Vec::new();
+    let mut index = 0;
+    while let Some(c) = code[index..].chars().next() {
+        let (class, end) = if let Some(found) = literal(code, index) {
+            found
+        } else if c == '\'' && code[index + 1..].starts_with(is_identifier) {
+            (Class::Lifetime, identifier_end(code, index + 1))
+        } else if c.is_ascii_digit() {
+            (Class::Literal, number_end(code, index))
+        } else if is_identifier(c) {
+            let end = identifier_end(code, index);
+            match &code[index..end] {
+                _ if code[end..].starts_with('!') && !code[end..].starts_with("!=") => {
+                    (Class::Macro, end + 1)
+                }
+                "true" | "false" => (Class::Literal, end),
+                word if KEYWORDS.contains(&word) => (Class::Keyword, end),
+                _ => (Class::Plain, end),
+            }
+        } else {
+            (Class::Plain, index + c.len_utf8())
+        };
+        match tokens.last_mut() {
+            Some((Class::Plain, _, last_end)) if class == Class::Plain => *last_end = end,
+            _ => tokens.push((class, index, end)),
+        }
+        index = end;
+    }
+    tokens
+        .into_iter()
+        .map(|(class, start, end)| (class, &code[start..end]))
+        .collect()
+}
+
+// `code` escaped for HTML, with every token but plain ones in a <span> with its class's name
+pub fn html(code: &str) -> String {
+    tokens(code)
+        .into_iter()
+        .map(|(class, text)| match class {
+            Class::Plain => escape_html(text),
+            _ => format!("<span class=\"{}\">{}</span>", class.name(), escape_html(text)),
+        })
+        .collect()
+}
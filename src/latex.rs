@@ -0,0 +1,153 @@
+use super::chapter::{Chapter, CHAPTERS};
+use super::render::{self, Level, Recorded};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// The handbook's main file. Chapters are numbered by LaTeX, in the same order as CHAPTERS, and
+// called Examples to match the titles the chapters print. Box-drawing characters in the listings
+// need a Unicode engine and a font that has them, hence LuaLaTeX and DejaVu Sans Mono.
+const PREAMBLE: &str = r"\documentclass[a4paper,11pt]{book}
+\usepackage{fontspec}
+\IfFontExistsTF{DejaVu Sans Mono}{\setmonofont{DejaVu Sans Mono}[Scale=MatchLowercase]}{}
+\usepackage{fancyvrb}
+\usepackage[hidelinks]{hyperref}
+\fvset{tabsize=4,fontsize=\small}
+\renewcommand{\chaptername}{Example}
+
+\title{Rust by Example}
+\date{}
+
+\begin{document}
+\maketitle
+\tableofcontents
+";
+
+// e.g. `03-custom-types`
+fn file_name(chapter: &dyn Chapter) -> String {
+    format!("{:02}-{}", chapter.number(), chapter.slug())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '{' | '}' | '$' | '&' | '#' | '%' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Narration with anything in `backticks` set in the code font. A backtick without another to
+// close it is just a backtick. Public for the tests.
+#[doc(hidden)]
+pub fn paragraph(text: &str) -> String {
+    let mut parts: Vec<&str> = text.split('`').collect();
+    let unmatched = if parts.len().is_multiple_of(2) { parts.pop() } else { None };
+    let mut latex: String = parts
+        .iter()
+        .enumerate()
+        .map(|(index, part)| match index % 2 {
+            0 => escape(part),
+            _ => format!(r"\texttt{{{}}}", escape(part)),
+        })
+        .collect();
+    if let Some(rest) = unmatched {
+        latex.push_str(r"\textasciigrave{}");
+        latex.push_str(&escape(rest));
+    }
+    latex
+}
+
+// Listings are set as they are, and what they print in a box
+fn verbatim(lines: &[&str], framed: bool) -> String {
+    let options = if framed { "[frame=single]" } else { "" };
+    format!(
+        "\\begin{{Verbatim}}{}\n{}\n\\end{{Verbatim}}\n\n",
+        options,
+        lines.join("\n").trim_matches('\n')
+    )
+}
+
+fn body(chapter: &dyn Chapter, events: &[Recorded]) -> String {
+    let mut latex = String::new();
+    let mut index = 0;
+    while index < events.len() {
+        let mut listing = Vec::new();
+        while let Some(Recorded::Listing(_, code)) = events.get(index) {
+            listing.push(code.as_str());
+            index += 1;
+        }
+        if !listing.is_empty() {
+            latex.push_str(&verbatim(&listing, false));
+        }
+        let mut output = Vec::new();
+        while let Some(Recorded::Output(text)) = events.get(index) {
+            output.push(text.as_str());
+            index += 1;
+        }
+        if !output.is_empty() {
+            latex.push_str(&verbatim(&output, true));
+        }
+        if !listing.is_empty() || !output.is_empty() {
+            continue;
+        }
+        match &events[index] {
+            Recorded::Title(Level::Example, _) => {
+                latex.push_str(&format!("\\chapter{{{}}}\n\n", escape(chapter.title())));
+            }
+            Recorded::Title(Level::Section, title) => {
+                latex.push_str(&format!("\\section{{{}}}\n\n", paragraph(title)));
+            }
+            Recorded::Title(Level::Subsection, title) => {
+                latex.push_str(&format!("\\subsection{{{}}}\n\n", paragraph(title)));
+            }
+            Recorded::Narration(text) => {
+                for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    latex.push_str(&format!("{}\n\n", paragraph(line)));
+                }
+            }
+            Recorded::Note(text) => {
+                latex.push_str(&format!("{{\\small\\itshape {}}}\n\n", paragraph(text.trim())));
+            }
+            Recorded::Listing(..) | Recorded::Output(_) => {}
+        }
+        index += 1;
+    }
+    latex
+}
+
+fn chapter_file(chapter: &dyn Chapter) -> String {
+    let mut events = render::record(|| chapter.show_intro());
+    for (depth, section) in chapter.outline() {
+        events.extend(render::record(|| section.show(depth)));
+    }
+    body(chapter, &events)
+}
+
+// Write the whole course to `dir` as a LaTeX handbook: main.tex, with the table of contents,
+// and a file for each chapter in chapters/. Returns every file written, main.tex last.
+pub fn export(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir.join("chapters"))?;
+    let mut written = Vec::new();
+    let mut main = String::from(PREAMBLE);
+    for &chapter in CHAPTERS.iter() {
+        let name = file_name(chapter);
+        let path = dir.join("chapters").join(format!("{}.tex", name));
+        fs::write(&path, chapter_file(chapter))?;
+        written.push(path);
+        main.push_str(&format!("\\include{{chapters/{}}}\n", name));
+    }
+    main.push_str("\\end{document}\n");
+    let path = dir.join("main.tex");
+    fs::write(&path, main)?;
+    written.push(path);
+    Ok(written)
+}
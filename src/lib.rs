@@ -0,0 +1,116 @@
+// The course as a library: every chapter, its sections, and what they print, for other programs
+// to use. The rust-by-example binary is built on it too.
+//
+//     for chapter in rust_by_example::chapters() {
+//         println!("{}. {}", chapter.number, chapter.title);
+//     }
+//     let text = rust_by_example::run_to_string("8/match_guards", Format::Plain)?.text;
+//
+// render_to runs chapters with their output going to any writer, and set_tee sends it to a
+// second one as well. mdbook::export, notebook::export and latex::export write the whole course
+// out as an mdBook, Jupyter notebooks or a LaTeX handbook.
+
+pub mod latex;
+pub mod mdbook;
+pub mod notebook;
+
+// How the chapters and the binary's commands work inside, which the binary and the tests need
+// but nothing else should rely on
+#[doc(hidden)]
+pub mod chapter;
+#[doc(hidden)]
+pub mod diagnostics;
+#[doc(hidden)]
+pub mod helpers;
+#[doc(hidden)]
+pub mod highlight;
+#[doc(hidden)]
+pub mod panics;
+#[doc(hidden)]
+pub mod render;
+#[doc(hidden)]
+pub mod rustc;
+#[doc(hidden)]
+pub mod source;
+
+mod a01_hello_world;
+mod a02_primitives;
+mod a03_custom_types;
+mod a04_variable_bindings;
+mod a05_types;
+mod a06_conversion;
+mod a07_expressions;
+mod a08_flow_control;
+mod a09_functions;
+mod a10_modules;
+mod a10_modules_as_files;
+mod a11_crates;
+mod a12_cargo;
+
+use chapter::Chapter;
+pub use panics::Panic;
+pub use render::{render_to, set_output, set_tee, Format, Notes, Theme};
+
+pub struct ChapterInfo {
+    // Its place in the course, from 1
+    pub number: usize,
+    // e.g. `custom-types`
+    pub slug: &'static str,
+    pub title: &'static str,
+    // Every section and subsection, in reading order
+    pub sections: Vec<SectionInfo>,
+}
+
+pub struct SectionInfo {
+    pub slug: &'static str,
+    pub title: &'static str,
+    // 0 for a top-level section, 1 for its subsections, and so on
+    pub depth: usize,
+    // What to pass to run_to_string for just this section, e.g. `3/linked_list`
+    pub name: String,
+}
+
+fn info(chapter: &dyn Chapter) -> ChapterInfo {
+    ChapterInfo {
+        number: chapter.number(),
+        slug: chapter.slug(),
+        title: chapter.title(),
+        sections: chapter
+            .outline()
+            .into_iter()
+            .map(|(depth, section)| SectionInfo {
+                slug: section.slug,
+                title: section.title,
+                depth,
+                name: format!("{}/{}", chapter.number(), section.slug),
+            })
+            .collect(),
+    }
+}
+
+// Every chapter in the course, in order
+pub fn chapters() -> Vec<ChapterInfo> {
+    chapter::CHAPTERS.iter().map(|&chapter| info(chapter)).collect()
+}
+
+pub struct Run {
+    // Everything the chapter or section printed
+    pub text: String,
+    // Each section that panicked when it wasn't meant to, by slug, and what it panicked with.
+    // The rest of the run carries on after one, so `text` is still complete.
+    pub panicked: Vec<(&'static str, Panic)>,
+}
+
+// Run a chapter or a section, named the way the `run` command takes them (`3`, `custom-types`,
+// `8/match_guards`...), and return everything it prints in `format`. A section comes with its
+// subsections.
+pub fn run_to_string(name: &str, format: Format) -> Result<Run, String> {
+    // Anything caught before, e.g. by an export on this thread, isn't this run's
+    panics::take();
+    let text = match chapter::lookup(name)? {
+        (_, Some((depth, section))) => render::capture(format, || section.run(depth)),
+        (chapter, None) => render::capture(format, || chapter.run()),
+    };
+    let (_, panicked) = panics::take();
+    Ok(Run { text, panicked })
+}
@@ -0,0 +1,193 @@
+use super::chapter::CHAPTERS;
+use super::diagnostics;
+use super::render::{self, Code, Recorded};
+use super::rustc;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+// Listings printed one straight after another, which read as one piece of code
+struct Listing {
+    // Where it's printed, e.g. `6/from_and_into`, or `6` for the chapter's intro
+    place: String,
+    kind: Code,
+    code: String,
+    // The Rust listings printed before it in the same place, which it can build on
+    context: Vec<String>,
+}
+
+// Every listing the chapters print, by running them all with their output recorded
+fn listings() -> Vec<Listing> {
+    let mut listings = Vec::new();
+    for &chapter in CHAPTERS.iter() {
+        let mut places = vec![(
+            chapter.number().to_string(),
+            render::record(|| chapter.show_intro()),
+        )];
+        for (depth, section) in chapter.outline() {
+            places.push((
+                format!("{}/{}", chapter.number(), section.slug),
+                render::record(|| section.show(depth)),
+            ));
+        }
+
+        for (place, events) in places {
+            let mut runs: Vec<(Code, String)> = Vec::new();
+            let mut in_run = false;
+            for event in events {
+                match event {
+                    Recorded::Listing(kind, code) => {
+                        match runs.last_mut() {
+                            Some((last, run)) if in_run && *last == kind => {
+                                run.push('\n');
+                                run.push_str(&code);
+                            }
+                            _ => runs.push((kind, code)),
+                        }
+                        in_run = true;
+                    }
+                    _ => in_run = false,
+                }
+            }
+
+            let mut context = Vec::new();
+            for (kind, code) in runs {
+                listings.push(Listing {
+                    place: place.clone(),
+                    kind,
+                    code: code.clone(),
+                    context: context.clone(),
+                });
+                if kind == Code::Rust {
+                    context.push(code);
+                }
+            }
+        }
+    }
+    listings
+}
+
+// Listings are mostly statements, sometimes items, and now and then something that only works
+// at the top of a crate, like `use crate::...`, so each is tried in a function and then on its own
+fn harnesses(listing: &Listing) -> [String; 2] {
+    let mut code = listing.context.join("\n");
+    code.push('\n');
+    code.push_str(&listing.code);
+    [
+        format!("fn main() {{\nlet _ = {{\n{}\n}};\n}}\n", code),
+        format!("{}\nfn main() {{}}\n", code),
+    ]
+}
+
+// Whether the listing compiles in either harness, and if it doesn't, what rustc said
+fn compiles(listing: &Listing, dir: &Path, index: usize) -> io::Result<Result<(), String>> {
+    let mut errors = None;
+    for (attempt, harness) in harnesses(listing).iter().enumerate() {
+        let source = dir.join(format!("listing_{}_{}.rs", index, attempt));
+        fs::write(&source, harness)?;
+        let output = rustc::check(&source, dir)?;
+        if output.status.success() {
+            return Ok(Ok(()));
+        }
+        errors.get_or_insert_with(|| String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(Err(errors.unwrap_or_default()))
+}
+
+// Just the first error, without the notes and help that follow it
+fn first_error(errors: &str) -> String {
+    let start = errors.find("error").unwrap_or(0);
+    let error = &errors[start..];
+    error[..error.find("\n\n").unwrap_or(error.len())].to_string()
+}
+
+fn indented(code: &str) -> String {
+    code.lines().map(|line| format!("\t{}\n", line)).collect()
+}
+
+// Compile every chapter's compile-fail cases and report any that compile, or that fail with a
+// different error from the one the chapter expects. Returns how many problems there were.
+fn check_compile_fails() -> io::Result<usize> {
+    let mut problems = 0;
+    for &chapter in CHAPTERS.iter() {
+        for case in chapter.compile_fails() {
+            let place = format!("{}/{}", chapter.number(), case.slug);
+            match diagnostics::first_error(case)? {
+                None => println!("\n{}: this is meant not to compile, but it does", place),
+                Some(error) if !error.contains(&format!("[{}]", case.error)) => println!(
+                    "\n{}: this is meant to fail with {}, but the compiler says\n\t{}",
+                    place, case.error, error
+                ),
+                Some(_) => continue,
+            }
+            problems += 1;
+        }
+    }
+    Ok(problems)
+}
+
+// Compile every Rust listing the chapters print and report any that don't compile, and any marked
+// compile_fail that do, then check the compile-fail cases. Returns how many problems there were.
+pub fn check() -> io::Result<usize> {
+    let listings: Vec<Listing> = listings()
+        .into_iter()
+        .filter(|listing| matches!(listing.kind, Code::Rust | Code::CompileFail))
+        .filter(|listing| !listing.code.trim().is_empty())
+        .collect();
+    println!("Checking {} listings with rustc...", listings.len());
+
+    let dir = rustc::ScratchDir::new("listings")?;
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let workers = thread::available_parallelism().map_or(4, |count| count.get());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (listings, next, dir) = (&listings, &next, &dir);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                match listings.get(index) {
+                    Some(listing) => {
+                        let _ = sender.send((index, compiles(listing, dir, index)));
+                    }
+                    None => break,
+                }
+            });
+        }
+    });
+    drop(sender);
+    let mut results: Vec<_> = receiver.into_iter().collect();
+    results.sort_by_key(|&(index, _)| index);
+
+    let mut problems = 0;
+    for (index, result) in results {
+        let listing = &listings[index];
+        match (listing.kind, result?) {
+            (Code::Rust, Err(errors)) => {
+                println!("\n{}: this listing doesn't compile", listing.place);
+                print!("{}", indented(&listing.code));
+                println!("{}", first_error(&errors));
+            }
+            (Code::CompileFail, Ok(())) => {
+                println!("\n{}: this listing is marked compile_fail, but it compiles", listing.place);
+                print!("{}", indented(&listing.code));
+            }
+            _ => continue,
+        }
+        problems += 1;
+    }
+    println!(
+        "\n{} of {} listings are fine",
+        listings.len() - problems,
+        listings.len()
+    );
+
+    let cases: usize = CHAPTERS.iter().map(|chapter| chapter.compile_fails().len()).sum();
+    println!("\nChecking {} compile-fail cases with rustc...", cases);
+    let failures = check_compile_fails()?;
+    println!("\n{} of {} compile-fail cases are fine", cases - failures, cases);
+    Ok(problems + failures)
+}
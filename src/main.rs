@@ -1,30 +1,364 @@
-mod helpers;
-
-// mod a01_hello_world;
-// mod a02_primitives;
-// mod a03_custom_types;
-// mod a04_variable_bindings;
-// mod a05_types;
-// mod a06_conversion;
-// mod a07_expressions;
-// mod a08_flow_control;
-// mod a09_functions;
-// mod a10_modules;
-// mod a10_modules_as_files;
-// mod a11_crates;
-mod a12_cargo;
+use chapter::{Chapter, Section};
+use render::{Format, Notes, Theme};
+use rust_by_example::{
+    chapter, diagnostics, highlight, latex, mdbook, notebook, panics, render, rustc,
+};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+
+// The chapters are in the library, and these are the commands for reading them
+mod exercise;
+mod listings;
+mod pager;
+mod progress;
+mod quiz;
+mod search;
+mod serve;
+mod site;
+
+const USAGE: &str = "Usage: rust-by-example [options] <command>
+
+Commands:
+    list              Show the table of contents
+    run <chapter>     Run a chapter by number (e.g. 3), name (e.g. custom-types), or `all`
+    run <chapter>/<section>
+                      Run one section of a chapter (e.g. 8/match_guards)
+    show <chapter>[/<section>]
+                      Print just the chapter's intro or just one section, without its subsections
+    step <chapter>[/<section>]
+                      Page through a chapter one section at a time
+    search <term>     Find the sections that talk about something, e.g. search while let
+    progress          Show how much of the course you've read
+    resume            Step through the course from the first section you haven't read
+    quiz <chapter>    Answer \"what does this print?\" questions about a chapter
+    exercise list     Show the exercises you can try
+    exercise start <chapter>/<exercise> [file]
+                      Write out an exercise to finish, as <exercise>.rs unless you name a file
+    exercise check <chapter>/<exercise> [file]
+                      Compile and run your answer, and compare it with what the chapter prints
+    site <dir>        Write every chapter to <dir> as a static website
+    serve [port]      Browse the course at http://127.0.0.1:<port>/ (8000 unless you give one),
+                      running each page again whenever it's loaded, rebuilt if you've edited it
+    export mdbook <dir>
+                      Write every chapter to <dir> as an mdBook source tree, a page per section
+    export ipynb <dir>
+                      Write each chapter to <dir> as a Jupyter notebook for a Rust kernel
+    export latex <dir>
+                      Write the course to <dir> as a LaTeX handbook to print
+    check-listings    Compile every code listing the chapters print, to make sure they're right
+
+Options:
+    --format <format> How to print chapters: plain, ansi, markdown, html or json.
+                      Defaults to ansi on a terminal and plain otherwise
+    --notes <style>   How to show notes about the output: inline, dim or hide.
+                      Defaults to dim, which is inline when there's no color
+    --tee <file>      Write chapters to <file> as well as the terminal, without color
+    --compile-errors  Compile the code that's meant not to, and show rustc's errors for it
+                      instead of just their codes
+    --theme <theme>   How titles are drawn and colored: ascii, unicode, minimal or high-contrast.
+                      Defaults to $RUST_BY_EXAMPLE_THEME, then the theme in config.txt in your
+                      config directory, then ascii
+
+Color is left off when $NO_COLOR is set, unless you ask for it with --format ansi";
+
+fn list() {
+    for chapter in rust_by_example::chapters() {
+        println!("{:>2}. {} ({})", chapter.number, chapter.title, chapter.slug);
+        for section in chapter.sections {
+            println!(
+                "{:indent$}{} ({})",
+                "",
+                section.title,
+                section.slug,
+                indent = 6 + section.depth * 4
+            );
+        }
+    }
+}
+
+// Reading is more important than remembering it, so a problem saving progress is only a warning
+fn record_progress(references: Vec<String>) {
+    if let Err(error) = progress::record(references) {
+        eprintln!("Couldn't save your progress: {}", error);
+    }
+}
+
+// Sections that panic are stopped so the rest can run, but the run as a whole has still failed
+fn panicked_sections() -> Result<(), String> {
+    match panics::summary() {
+        0 => Ok(()),
+        sections => Err(format!("Sections that panicked: {}", sections)),
+    }
+}
+
+fn run(name: &str) -> Result<(), String> {
+    if name == "all" {
+        for &chapter in chapter::CHAPTERS.iter() {
+            chapter.run();
+            record_progress(progress::chapter_references(chapter));
+        }
+        return panicked_sections();
+    }
+    match chapter::lookup(name)? {
+        (chapter, Some((depth, section))) => {
+            section.run(depth);
+            record_progress(
+                section
+                    .outline(depth)
+                    .into_iter()
+                    .map(|(_, section)| progress::reference(chapter, Some(section)))
+                    .collect(),
+            );
+        }
+        (chapter, None) => {
+            chapter.run();
+            record_progress(progress::chapter_references(chapter));
+        }
+    }
+    panicked_sections()
+}
+
+fn show(name: &str) -> Result<(), String> {
+    match chapter::lookup(name)? {
+        (_, Some((depth, section))) => section.show(depth),
+        (chapter, None) => chapter.show_intro(),
+    }
+    Ok(())
+}
+
+fn search(term: &str) -> Result<(), String> {
+    let hits = search::search(term);
+    search::print(&hits, term);
+    Ok(())
+}
+
+fn step_from(chapter: &dyn Chapter, section: Option<&'static Section>) -> Result<(), String> {
+    pager::step(chapter, section, |page| {
+        record_progress(vec![progress::reference(chapter, page)])
+    })
+    .map_err(|error| error.to_string())
+}
+
+fn step(name: &str) -> Result<(), String> {
+    let (chapter, section) = chapter::lookup(name)?;
+    step_from(chapter, section.map(|(_, section)| section))
+}
+
+fn show_progress() -> Result<(), String> {
+    let progress = progress::Progress::load().map_err(|error| error.to_string())?;
+    progress.print_summary();
+    Ok(())
+}
+
+fn resume() -> Result<(), String> {
+    let progress = progress::Progress::load().map_err(|error| error.to_string())?;
+    match progress.resume_point() {
+        Some((chapter, section)) => step_from(chapter, section),
+        None => {
+            println!("You've read the whole course! `step <chapter>` to read a chapter again.");
+            Ok(())
+        }
+    }
+}
+
+fn serve(port: Option<&str>) -> Result<(), String> {
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| format!("`{}` isn't a port number", port))?,
+        None => 8000,
+    };
+    serve::serve(port).map_err(|error| error.to_string())
+}
+
+fn quiz(name: &str) -> Result<(), String> {
+    let chapter = chapter::find(name).ok_or_else(|| format!("No chapter called `{}`", name))?;
+    quiz::run(chapter).map_err(|error| error.to_string())
+}
+
+fn exercise_start(name: &str, path: Option<&str>) -> Result<(), String> {
+    let (_, exercise) = exercise::lookup(name)?;
+    let path = path.map_or_else(|| exercise::default_path(exercise), PathBuf::from);
+    exercise::start(exercise, &path).map_err(|error| error.to_string())
+}
+
+fn exercise_check(name: &str, path: Option<&str>) -> Result<(), String> {
+    let (chapter, exercise) = exercise::lookup(name)?;
+    let path = path.map_or_else(|| exercise::default_path(exercise), PathBuf::from);
+    match exercise::check(chapter, exercise, &path) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Not quite there yet - keep going!".to_string()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+// Say what an export wrote, and how to use it
+fn exported(written: io::Result<Vec<PathBuf>>, dir: &str, next: &str) -> Result<(), String> {
+    let written = written.map_err(|error| error.to_string())?;
+    println!("Wrote {} files to {} - {}", written.len(), dir, next);
+    Ok(())
+}
+
+// Take `--<name> <value>` (or `--<name>=<value>`) out of the arguments, wherever it is
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let flag = format!("--{}", name);
+    let prefix = format!("--{}=", name);
+    let index = match args.iter().position(|arg| *arg == flag || arg.starts_with(&prefix)) {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let arg = args.remove(index);
+    match arg.strip_prefix(&prefix) {
+        Some(value) => Ok(Some(value.to_string())),
+        None if index < args.len() => Ok(Some(args.remove(index))),
+        None => Err(format!("{} needs a value", flag)),
+    }
+}
+
+// Take `--<name>` out of the arguments, wherever it is, and say whether it was there
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let flag = format!("--{}", name);
+    let before = args.len();
+    args.retain(|arg| *arg != flag);
+    args.len() != before
+}
+
+// https://no-color.org: any value but an empty one turns color off
+fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+fn take_format(args: &mut Vec<String>) -> Result<Format, String> {
+    match take_option(args, "format")? {
+        Some(name) => Format::parse(&name)
+            .ok_or_else(|| format!("Unknown format `{}`, try {}", name, render::FORMATS)),
+        None if io::stdout().is_terminal() && !no_color() => Ok(Format::Ansi),
+        None => Ok(Format::Plain),
+    }
+}
+
+fn take_notes(args: &mut Vec<String>) -> Result<Notes, String> {
+    match take_option(args, "notes")? {
+        Some(name) => Notes::parse(&name)
+            .ok_or_else(|| format!("Unknown note style `{}`, try {}", name, render::NOTE_STYLES)),
+        None => Ok(Notes::Dim),
+    }
+}
+
+// A `name = value` setting from config.txt in the config directory, if there is one
+fn config_setting(name: &str) -> Option<String> {
+    let config = fs::read_to_string(progress::config_dir().ok()?.join("config.txt")).ok()?;
+    config.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        Some(value.trim().to_string()).filter(|_| key.trim() == name)
+    })
+}
+
+// --theme wins over $RUST_BY_EXAMPLE_THEME, which wins over the config file
+fn take_theme(args: &mut Vec<String>) -> Result<Theme, String> {
+    let (name, from) = match take_option(args, "theme")? {
+        Some(name) => (name, "--theme"),
+        None => match env::var("RUST_BY_EXAMPLE_THEME") {
+            Ok(name) if !name.is_empty() => (name, "$RUST_BY_EXAMPLE_THEME"),
+            _ => match config_setting("theme") {
+                Some(name) => (name, "config.txt"),
+                None => return Ok(Theme::Ascii),
+            },
+        },
+    };
+    Theme::parse(&name)
+        .ok_or_else(|| format!("Unknown theme `{}` in {}, try {}", name, from, render::THEMES))
+}
+
+// With `--tee <file>`, chapter output goes to the file as well as the terminal, without color
+fn take_tee(
+    args: &mut Vec<String>,
+    format: Format,
+    notes: Notes,
+    theme: Theme,
+) -> Result<(), String> {
+    if let Some(path) = take_option(args, "tee")? {
+        let file = File::create(&path)
+            .map_err(|error| format!("Couldn't create {}: {}", path, error))?;
+        render::set_tee(format, notes, theme, Box::new(file));
+    }
+    Ok(())
+}
 
 fn main() {
-    // a01_hello_world::run();
-    // a02_primitives::run();
-    // a03_custom_types::run();
-    // a04_variable_bindings::run();
-    // a05_types::run();
-    // a06_conversion::run();
-    // a07_expressions::run();
-    // a08_flow_control::run();
-    // a09_functions::run();
-    // a10_modules::run();
-    // a11_crates::run();
-    a12_cargo::run();
-}
\ No newline at end of file
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    diagnostics::set_compile(take_flag(&mut args, "compile-errors"));
+    let options = take_format(&mut args).and_then(|format| {
+        let (notes, theme) = (take_notes(&mut args)?, take_theme(&mut args)?);
+        render::set_format(format, notes, theme);
+        take_tee(&mut args, format, notes, theme)
+    });
+    if let Err(message) = options {
+        eprintln!("{}", message);
+        process::exit(2);
+    }
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => {
+            list();
+            Ok(())
+        }
+        ["run", name] => run(name),
+        ["show", name] => show(name),
+        ["step", name] => step(name),
+        ["progress"] => show_progress(),
+        ["resume"] => resume(),
+        ["quiz", name] => quiz(name),
+        ["check-listings"] => match listings::check() {
+            Ok(0) => Ok(()),
+            Ok(problems) => Err(format!("Listings that need fixing: {}", problems)),
+            Err(error) => Err(error.to_string()),
+        },
+        ["site", dir] => site::build(Path::new(dir)).map_err(|error| error.to_string()),
+        ["serve"] => serve(None),
+        ["serve", port] => serve(Some(port)),
+        // What serve runs to answer each request
+        ["serve-page", method, path] => serve::page(method, path).map_err(|error| error.to_string()),
+        ["export", "mdbook", dir] => exported(
+            mdbook::export(Path::new(dir)),
+            dir,
+            &format!("build it with `mdbook build {}`", dir),
+        ),
+        ["export", "ipynb", dir] => exported(
+            notebook::export(Path::new(dir)),
+            dir,
+            "open them with Jupyter and the evcxr Rust kernel",
+        ),
+        ["export", "latex", dir] => exported(
+            latex::export(Path::new(dir)),
+            dir,
+            "build it with `latexmk -lualatex main.tex` in that directory",
+        ),
+        ["exercise", "list"] => {
+            exercise::list();
+            Ok(())
+        }
+        ["exercise", "start", name] => exercise_start(name, None),
+        ["exercise", "start", name, path] => exercise_start(name, Some(path)),
+        ["exercise", "check", name] => exercise_check(name, None),
+        ["exercise", "check", name, path] => exercise_check(name, Some(path)),
+        // A blank term would match everything
+        ["search", terms @ ..] if terms.iter().any(|term| !term.trim().is_empty()) => {
+            search(terms.join(" ").trim())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    // Output cut off by something like `| head` isn't worth complaining about
+    match render::finish() {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+            eprintln!("Couldn't write everything out: {}", error);
+            process::exit(1);
+        }
+        _ => {}
+    }
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(2);
+    }
+}
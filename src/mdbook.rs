@@ -0,0 +1,85 @@
+use super::chapter::{Chapter, Section, CHAPTERS};
+use super::render::{self, Format};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// The listings are fragments of bigger programs, so the playground's run button wouldn't work
+const BOOK_TOML: &str = "[book]
+title = \"Rust by Example\"
+src = \"src\"
+
+[output.html.playground]
+runnable = false
+";
+
+// e.g. `03-custom-types`, the directory a chapter's pages go in
+fn chapter_dir(chapter: &dyn Chapter) -> String {
+    format!("{:02}-{}", chapter.number(), chapter.slug())
+}
+
+// Pages are linked from SUMMARY.md relative to src/
+fn intro_page(chapter: &dyn Chapter) -> String {
+    format!("{}/index.md", chapter_dir(chapter))
+}
+
+fn section_page(chapter: &dyn Chapter, section: &Section) -> String {
+    format!("{}/{}.md", chapter_dir(chapter), section.slug)
+}
+
+// mdBook's table of contents: each chapter's intro, with its sections nested under it the way
+// their titles are nested in the chapter
+fn summary() -> String {
+    let mut summary = String::from("# Summary\n\n");
+    for &chapter in CHAPTERS.iter() {
+        summary.push_str(&format!(
+            "- [Example {}: {}]({})\n",
+            chapter.number(),
+            chapter.title(),
+            intro_page(chapter)
+        ));
+        for (depth, section) in chapter.outline() {
+            summary.push_str(&format!(
+                "{:indent$}- [{}]({})\n",
+                "",
+                section.title,
+                section_page(chapter, section),
+                indent = (depth + 1) * 4
+            ));
+        }
+    }
+    summary
+}
+
+// A section on its own page, with its title as the page's heading whatever level it is in the
+// chapter
+fn section_markdown(section: &Section, depth: usize) -> String {
+    let markdown = render::capture(Format::Markdown, || section.show(depth));
+    let heading = markdown.trim_start_matches('#');
+    format!("#{}", heading)
+}
+
+// Write the whole course to `dir` as an mdBook source tree: book.toml, and under src/ the
+// SUMMARY.md contents and a directory for each chapter, with a page for its intro and each section.
+// Returns every file written, in order.
+pub fn export(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let src = dir.join("src");
+    fs::create_dir_all(&src)?;
+    let mut written = Vec::new();
+    let mut write = |path: PathBuf, contents: String| {
+        fs::write(&path, contents)?;
+        written.push(path);
+        io::Result::Ok(())
+    };
+    write(dir.join("book.toml"), BOOK_TOML.to_string())?;
+    write(src.join("SUMMARY.md"), summary())?;
+    for &chapter in CHAPTERS.iter() {
+        fs::create_dir_all(src.join(chapter_dir(chapter)))?;
+        let intro = render::capture(Format::Markdown, || chapter.show_intro());
+        write(src.join(intro_page(chapter)), intro)?;
+        for (depth, section) in chapter.outline() {
+            write(src.join(section_page(chapter, section)), section_markdown(section, depth))?;
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,161 @@
+use super::chapter::{Chapter, CHAPTERS};
+use super::render::{self, escape_json, Code, Level, Recorded};
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+// For the evcxr Rust kernel, which is what Jupyter lists as "Rust"
+const METADATA: &str = r#""metadata": {
+  "kernelspec": {
+   "display_name": "Rust",
+   "language": "rust",
+   "name": "rust"
+  },
+  "language_info": {
+   "codemirror_mode": "rust",
+   "file_extension": ".rs",
+   "mimetype": "text/rust",
+   "name": "Rust",
+   "pygment_lexer": "rust"
+  }
+ }"#;
+
+// Listings taken from the chapters' own code print with the course's macros, so the first cell
+// defines them as plain println!s
+const SETUP: &str = "macro_rules! output { ($($arg:tt)*) => { println!($($arg)*) }; }
+macro_rules! output_inline { ($($arg:tt)*) => { print!($($arg)*) }; }
+macro_rules! narrate { ($($arg:tt)*) => { println!($($arg)*) }; }
+macro_rules! note { ($($arg:tt)*) => { println!($($arg)*) }; }";
+
+enum Cell {
+    Markdown(String),
+    // The code and what it printed when the chapter ran
+    Code(String, Vec<String>),
+}
+
+// Notebooks keep text as a list of lines, each but the last ending in a newline.
+// `indent` is how far in the list itself is.
+fn lines(text: &str, indent: usize) -> String {
+    let lines: Vec<String> = text
+        .split_inclusive('\n')
+        .map(|line| format!("{:2$}{}", "", escape_json(line), indent + 1))
+        .collect();
+    format!("[\n{}\n{:2$}]", lines.join(",\n"), "", indent)
+}
+
+impl Cell {
+    fn json(&self) -> String {
+        match self {
+            Cell::Markdown(text) => format!(
+                "  {{\n   \"cell_type\": \"markdown\",\n   \"metadata\": {{}},\n   \"source\": {}\n  }}",
+                lines(text.trim_end(), 3)
+            ),
+            Cell::Code(code, output) => {
+                let outputs = if output.is_empty() {
+                    "[]".to_string()
+                } else {
+                    format!(
+                        "[\n    {{\n     \"name\": \"stdout\",\n     \"output_type\": \"stream\",\n     \"text\": {}\n    }}\n   ]",
+                        lines(&(output.join("\n") + "\n"), 5)
+                    )
+                };
+                format!(
+                    "  {{\n   \"cell_type\": \"code\",\n   \"execution_count\": null,\n   \"metadata\": {{}},\n   \"outputs\": {},\n   \"source\": {}\n  }}",
+                    outputs,
+                    lines(code, 3)
+                )
+            }
+        }
+    }
+}
+
+// Whether a listing is Rust that can go in a code cell
+fn is_code(kind: Code) -> bool {
+    kind.language() == "rust" && kind != Code::Outline
+}
+
+// Output that doesn't follow any code goes in the Markdown, all in one fenced block
+fn fence_output(markdown: &mut String, output: &mut Vec<String>) {
+    if !output.is_empty() {
+        markdown.push_str(&format!("```text\n{}\n```\n\n", output.join("\n")));
+        output.clear();
+    }
+}
+
+// Narration, titles and notes are gathered into Markdown cells, and each run of Rust listings
+// becomes a code cell with the output that comes straight after it. Outlines, listings in other
+// languages, and output that doesn't follow any code are shown as fenced blocks in the Markdown.
+fn cells(events: Vec<Recorded>) -> Vec<Cell> {
+    let mut cells = vec![Cell::Code(SETUP.to_string(), Vec::new())];
+    let mut markdown = String::new();
+    let mut output = Vec::new();
+    for event in events {
+        if !matches!(event, Recorded::Output(_)) {
+            fence_output(&mut markdown, &mut output);
+        }
+        match event {
+            Recorded::Listing(kind, code) if is_code(kind) => match cells.last_mut() {
+                Some(Cell::Code(previous, output)) if markdown.is_empty() && output.is_empty() => {
+                    previous.push('\n');
+                    previous.push_str(&code);
+                }
+                _ => {
+                    if !markdown.is_empty() {
+                        cells.push(Cell::Markdown(mem::take(&mut markdown)));
+                    }
+                    cells.push(Cell::Code(code, Vec::new()));
+                }
+            },
+            Recorded::Output(text) => match cells.last_mut() {
+                Some(Cell::Code(_, printed)) if markdown.is_empty() => printed.push(text),
+                _ => output.push(text),
+            },
+            Recorded::Title(level, title) => {
+                let hashes = match level {
+                    Level::Example => "#",
+                    Level::Section => "##",
+                    Level::Subsection => "###",
+                };
+                markdown.push_str(&format!("{} {}\n\n", hashes, render::escape_markdown(&title)));
+            }
+            Recorded::Narration(text) => markdown.push_str(&render::markdown_narration(&text)),
+            Recorded::Note(text) => markdown.push_str(&render::markdown_note(&text)),
+            Recorded::Listing(kind, code) => {
+                markdown.push_str(&format!("```{}\n{}\n```\n\n", kind.language(), code))
+            }
+        }
+    }
+    fence_output(&mut markdown, &mut output);
+    if !markdown.is_empty() {
+        cells.push(Cell::Markdown(markdown));
+    }
+    cells
+}
+
+fn notebook(chapter: &dyn Chapter) -> String {
+    let mut events = render::record(|| chapter.show_intro());
+    for (depth, section) in chapter.outline() {
+        events.extend(render::record(|| section.show(depth)));
+    }
+    let cells: Vec<String> = cells(events).iter().map(Cell::json).collect();
+    format!(
+        "{{\n \"cells\": [\n{}\n ],\n {},\n \"nbformat\": 4,\n \"nbformat_minor\": 4\n}}\n",
+        cells.join(",\n"),
+        METADATA
+    )
+}
+
+// Write each chapter to `dir` as a Jupyter notebook, e.g. `03-custom-types.ipynb`, with the
+// output of every code cell already filled in from running the chapter. Returns the notebooks
+// written, in course order.
+pub fn export(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for &chapter in CHAPTERS.iter() {
+        let path = dir.join(format!("{:02}-{}.ipynb", chapter.number(), chapter.slug()));
+        fs::write(&path, notebook(chapter))?;
+        written.push(path);
+    }
+    Ok(written)
+}
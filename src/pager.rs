@@ -0,0 +1,71 @@
+use super::chapter::{Chapter, Section};
+use super::render;
+use std::io::{self, BufRead, Write};
+
+enum Key {
+    Next,
+    Previous,
+    Repeat,
+    Quit,
+}
+
+// Waits for a line on stdin; just pressing enter means "next", and running out of input means "quit"
+fn read_key(position: usize, pages: usize) -> io::Result<Key> {
+    let stdin = io::stdin();
+    render::flush();
+    loop {
+        print!("\n[{}/{}] (n)ext, (p)revious, (r)epeat, (q)uit: ", position, pages);
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(Key::Quit);
+        }
+        match line.trim() {
+            "" | "n" => return Ok(Key::Next),
+            "p" => return Ok(Key::Previous),
+            "r" => return Ok(Key::Repeat),
+            "q" => return Ok(Key::Quit),
+            _ => continue,
+        }
+    }
+}
+
+// Show a chapter one page at a time: the intro first, then each section and subsection on its own,
+// pausing at each section boundary. Starts from `start` if it's given.
+// `on_page` is called with each page as it's shown, None being the intro.
+pub fn step(
+    chapter: &dyn Chapter,
+    start: Option<&'static Section>,
+    mut on_page: impl FnMut(Option<&'static Section>),
+) -> io::Result<()> {
+    let sections = chapter.outline();
+    let pages = sections.len() + 1;
+    let mut page = match start {
+        Some(start) => 1 + sections
+            .iter()
+            .position(|&(_, section)| section.slug == start.slug)
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    loop {
+        if page == 0 {
+            chapter.show_intro();
+            on_page(None);
+        } else {
+            let (depth, section) = sections[page - 1];
+            section.show(depth);
+            on_page(Some(section));
+        }
+
+        match read_key(page + 1, pages)? {
+            Key::Next if page + 1 == pages => return Ok(()),
+            Key::Next => page += 1,
+            Key::Previous => page = page.saturating_sub(1),
+            Key::Repeat => {}
+            Key::Quit => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::render::{note, output};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+// Running chapter code so that a panic is shown like any other output and the run carries on,
+// instead of the panic printing to stderr and ending the whole session
+
+pub struct Panic {
+    pub message: String,
+    // e.g. src/a02_primitives.rs:280:23
+    pub location: String,
+}
+
+// Every panic caught since the summary was last taken, and whether the chapter meant it
+struct Caught {
+    panic: Panic,
+    // The section that panicked when it wasn't meant to, None when it was shown on purpose
+    section: Option<&'static str>,
+}
+
+thread_local! {
+    // How many catch() calls deep this thread is. The hook only keeps quiet inside one.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    // What the hook saw of the panic being caught, for catch() to pick up once it's unwound
+    static LAST: RefCell<Option<Panic>> = const { RefCell::new(None) };
+    static CAUGHT: RefCell<Vec<Caught>> = const { RefCell::new(Vec::new()) };
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "Box<dyn Any>".to_string(),
+    }
+}
+
+// Panics outside catch() still go to the default hook, so real bugs are reported as usual
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) == 0 {
+                return default(info);
+            }
+            let panic = Panic {
+                message: message(info.payload()),
+                location: info
+                    .location()
+                    .map_or_else(String::new, |location| location.to_string()),
+            };
+            LAST.with(|last| *last.borrow_mut() = Some(panic));
+        }));
+    });
+}
+
+// Run `run`, returning what it panicked with if it panics
+pub fn catch<T>(run: impl FnOnce() -> T) -> Result<T, Panic> {
+    install_hook();
+    CATCHING.with(|catching| catching.set(catching.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(run));
+    CATCHING.with(|catching| catching.set(catching.get() - 1));
+    result.map_err(|payload| {
+        LAST.with(|last| last.borrow_mut().take()).unwrap_or_else(|| Panic {
+            message: message(&*payload),
+            location: String::new(),
+        })
+    })
+}
+
+fn print(panic: &Panic) {
+    output!("panicked at {}:", panic.location);
+    output!("{}", panic.message);
+}
+
+// Run code that's meant to panic and show what it panicked with, like the message Rust prints
+pub fn show(run: impl FnOnce()) {
+    match catch(run) {
+        Ok(()) => note!("(This is meant to panic, but it didn't)"),
+        Err(panic) => {
+            print(&panic);
+            CAUGHT.with(|caught| caught.borrow_mut().push(Caught { panic, section: None }));
+        }
+    }
+}
+
+// Run a section, showing a panic it wasn't meant to have so the sections after it still run
+pub fn run_section(slug: &'static str, run: fn()) {
+    if let Err(panic) = catch(run) {
+        note!("(The {} section panicked and was stopped here)", slug);
+        print(&panic);
+        let section = Some(slug);
+        CAUGHT.with(|caught| caught.borrow_mut().push(Caught { panic, section }));
+    }
+}
+
+// Take every panic caught since this was last called, starting the count again: how many were
+// shown on purpose, and each section that panicked when it wasn't meant to, with its panic
+pub fn take() -> (usize, Vec<(&'static str, Panic)>) {
+    let caught = CAUGHT.with(|caught| caught.take());
+    let shown = caught.iter().filter(|caught| caught.section.is_none()).count();
+    let failed = caught
+        .into_iter()
+        .filter_map(|caught| Some((caught.section?, caught.panic)))
+        .collect();
+    (shown, failed)
+}
+
+// After a run, say how many panics were shown on purpose and list any sections that panicked,
+// then start counting again. Says nothing if there weren't any.
+// Returns how many sections panicked.
+pub fn summary() -> usize {
+    let (shown, failed) = take();
+    if shown == 0 && failed.is_empty() {
+        return 0;
+    }
+    note!(
+        "\n(Panics shown on purpose: {}. Sections that panicked: {})",
+        shown,
+        failed.len()
+    );
+    for (section, panic) in &failed {
+        note!("({} panicked at {}: {})", section, panic.location, panic.message);
+    }
+    failed.len()
+}
@@ -0,0 +1,210 @@
+use super::chapter::{Chapter, Section, CHAPTERS};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// When a page (a chapter intro or a section) was first and last read, in seconds since the epoch
+struct Visit {
+    first: u64,
+    last: u64,
+}
+
+// Everything one person has read, keyed by page reference like `flow-control/match_guards`.
+// References use slugs rather than numbers so progress survives chapters being reordered.
+pub struct Progress {
+    visits: HashMap<String, Visit>,
+}
+
+pub fn reference(chapter: &dyn Chapter, section: Option<&Section>) -> String {
+    match section {
+        Some(section) => format!("{}/{}", chapter.slug(), section.slug),
+        None => chapter.slug().to_string(),
+    }
+}
+
+// The chapter's intro and every section in it, in reading order
+pub fn chapter_references(chapter: &dyn Chapter) -> Vec<String> {
+    let mut references = vec![reference(chapter, None)];
+    for (_, section) in chapter.outline() {
+        references.push(reference(chapter, Some(section)));
+    }
+    references
+}
+
+// Every page in the course in reading order
+fn pages() -> Vec<(&'static dyn Chapter, Option<&'static Section>)> {
+    let mut pages = Vec::new();
+    for &chapter in CHAPTERS.iter() {
+        pages.push((chapter, None));
+        for (_, section) in chapter.outline() {
+            pages.push((chapter, Some(section)));
+        }
+    }
+    pages
+}
+
+// $XDG_CONFIG_HOME/rust-by-example, falling back to ~/.config/rust-by-example
+pub fn config_dir() -> io::Result<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => match env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+            Some(home) => PathBuf::from(home).join(".config"),
+            None => return Err(io::Error::other("can't find a home directory")),
+        },
+    };
+    Ok(base.join("rust-by-example"))
+}
+
+pub fn path() -> io::Result<PathBuf> {
+    Ok(config_dir()?.join("progress.txt"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+// Seconds since the epoch as `YYYY-MM-DD HH:MM UTC`, using Howard Hinnant's days-to-civil algorithm
+fn format_time(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let minutes_today = (seconds % 86_400) / 60;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes_today / 60,
+        minutes_today % 60
+    )
+}
+
+impl Progress {
+    // A missing file just means nothing has been read yet
+    pub fn load() -> io::Result<Progress> {
+        let text = match fs::read_to_string(path()?) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+        let mut visits = HashMap::new();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if let [reference, first, last] = fields.as_slice() {
+                if let (Ok(first), Ok(last)) = (first.parse(), last.parse()) {
+                    visits.insert(reference.to_string(), Visit { first, last });
+                }
+            }
+        }
+        Ok(Progress { visits })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Written in course order so the file is easy to read by hand
+        let mut text = String::new();
+        for (chapter, section) in pages() {
+            let reference = reference(chapter, section);
+            if let Some(visit) = self.visits.get(&reference) {
+                text.push_str(&format!("{}\t{}\t{}\n", reference, visit.first, visit.last));
+            }
+        }
+        fs::write(path, text)
+    }
+
+    pub fn visit(&mut self, reference: String) {
+        let now = now();
+        self.visits
+            .entry(reference)
+            .and_modify(|visit| visit.last = now)
+            .or_insert(Visit {
+                first: now,
+                last: now,
+            });
+    }
+
+    // The first unread page after the one read most recently, wrapping around to the start.
+    // None when everything has been read.
+    pub fn resume_point(&self) -> Option<(&'static dyn Chapter, Option<&'static Section>)> {
+        let pages = pages();
+        let latest = pages
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &(chapter, section))| {
+                self.visits
+                    .get(&reference(chapter, section))
+                    .map(|visit| (visit.last, index))
+            })
+            .max()
+            .map_or(0, |(_, index)| index + 1);
+        (0..pages.len())
+            .map(|offset| pages[(latest + offset) % pages.len()])
+            .find(|&(chapter, section)| !self.visits.contains_key(&reference(chapter, section)))
+    }
+
+    pub fn print_summary(&self) {
+        let mut read = 0;
+        let mut total = 0;
+        for &chapter in CHAPTERS.iter() {
+            let references = chapter_references(chapter);
+            let visits: Vec<&Visit> = references
+                .iter()
+                .filter_map(|reference| self.visits.get(reference))
+                .collect();
+            read += visits.len();
+            total += references.len();
+
+            let last = match visits.iter().map(|visit| visit.last).max() {
+                Some(last) => format!("last read {}", format_time(last)),
+                None => String::new(),
+            };
+            let started = match visits.iter().map(|visit| visit.first).min() {
+                Some(first) => format!(", started {}", format_time(first)),
+                None => String::new(),
+            };
+            let line = format!(
+                "{:>2}. {:<20} {:>2}/{:<2} {}{}",
+                chapter.number(),
+                chapter.title(),
+                visits.len(),
+                references.len(),
+                last,
+                started
+            );
+            println!("{}", line.trim_end());
+        }
+        println!("\n{} of {} pages read", read, total);
+        match self.resume_point() {
+            Some((chapter, section)) => {
+                println!("`resume` will pick up at {}", reference(chapter, section))
+            }
+            None => println!("That's the whole course!"),
+        }
+    }
+}
+
+// Mark pages as read, saving straight away so nothing is lost if the reader quits part way
+pub fn record(references: Vec<String>) -> io::Result<()> {
+    let mut progress = Progress::load()?;
+    for reference in references {
+        progress.visit(reference);
+    }
+    progress.save()
+}
@@ -0,0 +1,62 @@
+use super::chapter::{Chapter, Question};
+use std::io::{self, BufRead, Write};
+
+pub struct Score {
+    pub correct: usize,
+    pub asked: usize,
+}
+
+// Answers are compared without surrounding whitespace, since that's hard to see and type
+fn is_correct(question: &Question, answer: &str) -> bool {
+    question.answer.trim() == answer.trim()
+}
+
+// Ask each question in turn, reading answers a line at a time from `input`.
+// Running out of input ends the quiz early, and only the questions asked so far are scored.
+pub fn ask(questions: &[Question], input: &mut impl BufRead) -> io::Result<Score> {
+    let mut score = Score {
+        correct: 0,
+        asked: 0,
+    };
+    for (number, question) in questions.iter().enumerate() {
+        println!("\nQuestion {} of {}: what does this print?", number + 1, questions.len());
+        // stringify! wraps long code over several lines, so put it back on one
+        let code: Vec<&str> = question.code.split_whitespace().collect();
+        println!("\t{}", code.join(" "));
+        print!("> ");
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            println!();
+            break;
+        }
+        score.asked += 1;
+        if is_correct(question, &answer) {
+            score.correct += 1;
+            println!("Correct!");
+        } else {
+            println!("Not quite, it prints: {}", question.answer);
+        }
+    }
+    Ok(score)
+}
+
+pub fn run(chapter: &dyn Chapter) -> io::Result<()> {
+    let questions = chapter.quiz();
+    if questions.is_empty() {
+        println!("There's no quiz for chapter {} yet", chapter.number());
+        return Ok(());
+    }
+    println!("Example {} quiz: {}", chapter.number(), chapter.title());
+    let score = ask(&questions, &mut io::stdin().lock())?;
+    if score.asked > 0 {
+        println!(
+            "\nYou got {} of {} right ({}%)",
+            score.correct,
+            score.asked,
+            score.correct * 100 / score.asked
+        );
+    }
+    Ok(())
+}
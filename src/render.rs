@@ -0,0 +1,908 @@
+use super::helpers;
+use super::highlight::{self, Class};
+use super::source;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::mem;
+use std::rc::Rc;
+
+// Everything a chapter prints is one of these. The renderer for the chosen --format decides
+// what each looks like, so chapters never print anything themselves.
+#[derive(Clone, Copy)]
+pub enum Event<'a> {
+    Title(Level, &'a str),
+    // Explanation written for the reader
+    Narration(&'a str),
+    // Code being talked about, without any indentation
+    Listing(Code, &'a str),
+    // What running the example code produced
+    Output(&'a str),
+    // An aside about the output just printed, e.g. which arguments produced it
+    Note(&'a str),
+}
+
+// What's in a listing. Rust listings are checked to compile, so anything that isn't meant to,
+// like an outline with `...` in it, has to say so.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Code {
+    Rust,
+    // Taken from code the chapter really runs with show_and_run!, so it's known to compile
+    Ran,
+    CompileFail,
+    // The shape of some Rust rather than the real thing, with `...` for the parts left out, so it
+    // isn't compiled at all
+    Outline,
+    // Not Rust at all, e.g. `toml` for Cargo.toml or `sh` for a shell command
+    Other(&'static str),
+}
+
+impl Code {
+    // The name code blocks are tagged with, e.g. ```toml
+    pub fn language(self) -> &'static str {
+        match self {
+            Code::Rust | Code::Ran | Code::CompileFail | Code::Outline => "rust",
+            Code::Other(language) => language,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Level {
+    Example,
+    Section,
+    Subsection,
+}
+
+pub trait Renderer {
+    fn event(&mut self, out: &mut dyn Write, event: Event) -> io::Result<()>;
+    // Close anything left open, e.g. a code block, before something else is printed
+    fn flush(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+    // Called once when all chapter output is done
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        self.flush(out)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Plain,
+    Ansi,
+    Markdown,
+    Html,
+    Json,
+}
+
+pub const FORMATS: &str = "plain, ansi, markdown, html or json";
+
+// How notes are shown. Dim is the same as inline where there's no color.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Notes {
+    Inline,
+    Dim,
+    Hide,
+}
+
+pub const NOTE_STYLES: &str = "inline, dim or hide";
+
+// How titles are drawn and what colors things are in the plain and ansi formats
+#[derive(Clone, Copy, PartialEq)]
+pub enum Theme {
+    // The box art the chapters have always printed
+    Ascii,
+    // The same boxes drawn with box-drawing characters
+    Unicode,
+    // Just the words, for screen readers
+    Minimal,
+    // Bright, bold colors, and notes that aren't dimmed
+    HighContrast,
+}
+
+pub const THEMES: &str = "ascii, unicode, minimal or high-contrast";
+
+impl Theme {
+    pub fn parse(name: &str) -> Option<Theme> {
+        match name {
+            "ascii" => Some(Theme::Ascii),
+            "unicode" => Some(Theme::Unicode),
+            "minimal" => Some(Theme::Minimal),
+            "high-contrast" => Some(Theme::HighContrast),
+            _ => None,
+        }
+    }
+
+    // The ANSI color for each kind of event
+    fn color(self, event: &Event) -> &'static str {
+        let high_contrast = self == Theme::HighContrast;
+        match (event, high_contrast) {
+            (Event::Title(Level::Example, _), false) => "1;35",
+            (Event::Title(Level::Example, _), true) => "1;93",
+            (Event::Title(Level::Section, _), false) => "1;34",
+            (Event::Title(Level::Section, _), true) => "1;96",
+            (Event::Title(Level::Subsection, _), false) => "1",
+            (Event::Title(Level::Subsection, _), true) => "1;97",
+            (Event::Listing(..), false) => "36",
+            (Event::Listing(..), true) => "1;96",
+            (Event::Output(_), false) => "32",
+            (Event::Output(_), true) => "1;92",
+            (Event::Note(_), false) => "2",
+            (Event::Note(_), true) => "97",
+            (Event::Narration(_), _) => "0",
+        }
+    }
+
+    // The ANSI color for each class of token in a Rust listing
+    fn token_color(self, class: Class) -> &'static str {
+        let high_contrast = self == Theme::HighContrast;
+        match (class, high_contrast) {
+            (Class::Plain, false) => "36",
+            (Class::Plain, true) => "1;96",
+            (Class::Keyword, false) => "1;35",
+            (Class::Keyword, true) => "1;95",
+            (Class::Literal, false) => "33",
+            (Class::Literal, true) => "1;93",
+            (Class::String, false) => "32",
+            (Class::String, true) => "1;92",
+            (Class::Lifetime, false) => "3;36",
+            (Class::Lifetime, true) => "1;3;96",
+            (Class::Macro, false) => "1;34",
+            (Class::Macro, true) => "1;94",
+            (Class::Comment, false) => "2;3",
+            (Class::Comment, true) => "3;97",
+        }
+    }
+}
+
+impl Notes {
+    pub fn parse(name: &str) -> Option<Notes> {
+        match name {
+            "inline" => Some(Notes::Inline),
+            "dim" => Some(Notes::Dim),
+            "hide" => Some(Notes::Hide),
+            _ => None,
+        }
+    }
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "plain" => Some(Format::Plain),
+            "ansi" => Some(Format::Ansi),
+            "markdown" => Some(Format::Markdown),
+            "html" => Some(Format::Html),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    // `width` is the terminal's, for the text formats to wrap narration to
+    fn renderer(self, notes: Notes, theme: Theme, width: Option<usize>) -> Box<dyn Renderer> {
+        match self {
+            Format::Plain => Box::new(Text {
+                color: false,
+                notes,
+                theme,
+                width,
+            }),
+            Format::Ansi => Box::new(Text {
+                color: true,
+                notes,
+                theme,
+                width,
+            }),
+            Format::Markdown => Box::new(Markdown { open: None }),
+            Format::Html => Box::new(Html {
+                started: false,
+                open: None,
+            }),
+            Format::Json => Box::new(Json),
+        }
+    }
+}
+
+// Titles drawn in the theme's art, optionally in its colors
+struct Text {
+    color: bool,
+    notes: Notes,
+    theme: Theme,
+    // How many columns to wrap narration at, None to leave it alone
+    width: Option<usize>,
+}
+
+impl Text {
+    fn paint(&self, out: &mut dyn Write, event: &Event) -> io::Result<()> {
+        if self.color {
+            write!(out, "\x1b[{}m", self.theme.color(event))?;
+        }
+        Ok(())
+    }
+
+    fn reset(&self, out: &mut dyn Write) -> io::Result<()> {
+        if self.color {
+            write!(out, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+impl Renderer for Text {
+    fn event(&mut self, out: &mut dyn Write, event: Event) -> io::Result<()> {
+        match event {
+            Event::Title(level, title) => {
+                self.paint(out, &event)?;
+                match level {
+                    Level::Example => helpers::example_title(out, self.theme, title)?,
+                    Level::Section => helpers::section_title(out, self.theme, title)?,
+                    Level::Subsection => helpers::section_subtitle(out, self.theme, title)?,
+                }
+                self.reset(out)?;
+                writeln!(out)
+            }
+            Event::Narration(text) => match self.width {
+                Some(columns) => writeln!(out, "{}", helpers::wrap(text, columns)),
+                None => writeln!(out, "{}", text),
+            },
+            // Rust is highlighted when there's color, and everything else is in one color
+            Event::Listing(kind, code) if self.color && kind.language() == "rust" => {
+                write!(out, "\t")?;
+                for (class, text) in highlight::tokens(code) {
+                    for (index, part) in text.split('\n').enumerate() {
+                        if index > 0 {
+                            write!(out, "\n\t")?;
+                        }
+                        if !part.is_empty() {
+                            let color = self.theme.token_color(class);
+                            write!(out, "\x1b[{}m{}\x1b[0m", color, part)?;
+                        }
+                    }
+                }
+                writeln!(out)
+            }
+            Event::Listing(_, code) => {
+                for line in code.split('\n') {
+                    write!(out, "\t")?;
+                    self.paint(out, &event)?;
+                    write!(out, "{}", line)?;
+                    self.reset(out)?;
+                    writeln!(out)?;
+                }
+                Ok(())
+            }
+            Event::Output(text) => {
+                self.paint(out, &event)?;
+                write!(out, "{}", text)?;
+                self.reset(out)?;
+                writeln!(out)
+            }
+            Event::Note(text) => {
+                if self.notes == Notes::Dim {
+                    self.paint(out, &event)?;
+                }
+                write!(out, "{}", text)?;
+                self.reset(out)?;
+                writeln!(out)
+            }
+        }
+    }
+}
+
+// Which kind of fenced or <pre> block is currently open
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    Listing(Code),
+    Output,
+}
+
+struct Markdown {
+    open: Option<Block>,
+}
+
+impl Markdown {
+    fn open(&mut self, out: &mut dyn Write, block: Block) -> io::Result<()> {
+        if self.open != Some(block) {
+            self.flush(out)?;
+            match block {
+                Block::Listing(kind) => writeln!(out, "```{}", kind.language())?,
+                Block::Output => writeln!(out, "```text")?,
+            }
+            self.open = Some(block);
+        }
+        Ok(())
+    }
+}
+
+impl Renderer for Markdown {
+    fn event(&mut self, out: &mut dyn Write, event: Event) -> io::Result<()> {
+        match event {
+            Event::Title(level, title) => {
+                self.flush(out)?;
+                let hashes = match level {
+                    Level::Example => "#",
+                    Level::Section => "##",
+                    Level::Subsection => "###",
+                };
+                writeln!(out, "{} {}\n", hashes, escape_markdown(title))
+            }
+            Event::Narration(text) => {
+                self.flush(out)?;
+                write!(out, "{}", markdown_narration(text))
+            }
+            Event::Listing(kind, code) => {
+                self.open(out, Block::Listing(kind))?;
+                writeln!(out, "{}", code)
+            }
+            Event::Output(text) => {
+                self.open(out, Block::Output)?;
+                writeln!(out, "{}", text)
+            }
+            Event::Note(text) => {
+                self.flush(out)?;
+                write!(out, "{}", markdown_note(text))
+            }
+        }
+    }
+
+    fn flush(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        if self.open.take().is_some() {
+            writeln!(out, "```\n")?;
+        }
+        Ok(())
+    }
+}
+
+// Text for Markdown. Anything in `backticks` is kept as code, and everything else Markdown would
+// read as HTML, emphasis or a link, like the `<T>` in `fn apply<T>` outside of backticks, is
+// escaped. A backtick without another to close it is just a backtick.
+pub fn escape_markdown(text: &str) -> String {
+    let mut parts: Vec<&str> = text.split('`').collect();
+    let unmatched = if parts.len().is_multiple_of(2) { parts.pop() } else { None };
+    let mut escaped = String::with_capacity(text.len());
+    for (index, part) in parts.iter().enumerate() {
+        if index % 2 == 1 && !part.is_empty() {
+            escaped.push('`');
+            escaped.push_str(part);
+            escaped.push('`');
+            continue;
+        }
+        if index % 2 == 1 {
+            escaped.push_str("\\`\\`");
+        }
+        for c in part.chars() {
+            if let '\\' | '<' | '>' | '&' | '*' | '_' | '[' | ']' = c {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+    if let Some(rest) = unmatched {
+        escaped.push_str("\\`");
+        escaped.push_str(&escape_markdown(rest));
+    }
+    escaped
+}
+
+// Narration as Markdown, a paragraph for each line. Leading tabs would turn it into an indented
+// code block, so lines are trimmed.
+pub fn markdown_narration(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("{}\n\n", escape_markdown(line)))
+        .collect()
+}
+
+// A note as a Markdown quote
+pub fn markdown_note(text: &str) -> String {
+    let mut quote: String = text
+        .lines()
+        .map(|line| format!("> {}\n", escape_markdown(line.trim())))
+        .collect();
+    quote.push('\n');
+    quote
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// A complete page: the <head> goes out before the first event and the closing tags in finish()
+struct Html {
+    started: bool,
+    open: Option<Block>,
+}
+
+impl Html {
+    fn open(&mut self, out: &mut dyn Write, block: Block) -> io::Result<()> {
+        if self.open != Some(block) {
+            self.flush(out)?;
+            match block {
+                Block::Listing(kind) => {
+                    write!(out, "<pre><code class=\"language-{}\">", kind.language())?
+                }
+                Block::Output => write!(out, "<pre class=\"output\"><samp>")?,
+            }
+            self.open = Some(block);
+        }
+        Ok(())
+    }
+}
+
+impl Renderer for Html {
+    fn event(&mut self, out: &mut dyn Write, event: Event) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">")?;
+            writeln!(out, "<title>Rust by Example</title>\n</head>\n<body>")?;
+        }
+        match event {
+            Event::Title(level, title) => {
+                self.flush(out)?;
+                let tag = match level {
+                    Level::Example => "h1",
+                    Level::Section => "h2",
+                    Level::Subsection => "h3",
+                };
+                writeln!(out, "<{0}>{1}</{0}>", tag, escape_html(title))
+            }
+            Event::Narration(text) => {
+                self.flush(out)?;
+                let text = text.trim();
+                if !text.is_empty() {
+                    writeln!(out, "<p>{}</p>", escape_html(text))?;
+                }
+                Ok(())
+            }
+            Event::Listing(kind, code) => {
+                self.open(out, Block::Listing(kind))?;
+                writeln!(out, "{}", escape_html(code))
+            }
+            Event::Output(text) => {
+                self.open(out, Block::Output)?;
+                writeln!(out, "{}", escape_html(text))
+            }
+            Event::Note(text) => {
+                self.flush(out)?;
+                writeln!(out, "<pre class=\"note\"><small>{}</small></pre>", escape_html(text))
+            }
+        }
+    }
+
+    fn flush(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        match self.open.take() {
+            Some(Block::Listing(_)) => writeln!(out, "</code></pre>"),
+            Some(Block::Output) => writeln!(out, "</samp></pre>"),
+            None => Ok(()),
+        }
+    }
+
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        self.flush(out)?;
+        if self.started {
+            writeln!(out, "</body>\n</html>")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// One JSON object per line, e.g. {"type":"listing","text":"let x = 5;"}
+struct Json;
+
+impl Renderer for Json {
+    fn event(&mut self, out: &mut dyn Write, event: Event) -> io::Result<()> {
+        match event {
+            Event::Title(level, title) => {
+                let level = match level {
+                    Level::Example => "example",
+                    Level::Section => "section",
+                    Level::Subsection => "subsection",
+                };
+                writeln!(
+                    out,
+                    "{{\"type\":\"title\",\"level\":\"{}\",\"text\":{}}}",
+                    level,
+                    escape_json(title)
+                )
+            }
+            Event::Narration(text) => {
+                writeln!(out, "{{\"type\":\"narration\",\"text\":{}}}", escape_json(text))
+            }
+            Event::Listing(kind, code) => writeln!(
+                out,
+                "{{\"type\":\"listing\",\"language\":\"{}\",\"compile_fail\":{},\"outline\":{},\"text\":{}}}",
+                kind.language(),
+                kind == Code::CompileFail,
+                kind == Code::Outline,
+                escape_json(code)
+            ),
+            Event::Output(text) => {
+                writeln!(out, "{{\"type\":\"output\",\"text\":{}}}", escape_json(text))
+            }
+            Event::Note(text) => {
+                writeln!(out, "{{\"type\":\"note\",\"text\":{}}}", escape_json(text))
+            }
+        }
+    }
+}
+
+// An event kept to be laid out later, once everything around it is known
+pub enum Recorded {
+    Title(Level, String),
+    Narration(String),
+    Listing(Code, String),
+    Output(String),
+    Note(String),
+}
+
+struct Recorder {
+    events: Rc<RefCell<Vec<Recorded>>>,
+}
+
+impl Renderer for Recorder {
+    fn event(&mut self, _out: &mut dyn Write, event: Event) -> io::Result<()> {
+        self.events.borrow_mut().push(match event {
+            Event::Title(level, title) => Recorded::Title(level, title.to_string()),
+            Event::Narration(text) => Recorded::Narration(text.to_string()),
+            Event::Listing(kind, code) => Recorded::Listing(kind, code.to_string()),
+            Event::Output(text) => Recorded::Output(text.to_string()),
+            Event::Note(text) => Recorded::Note(text.to_string()),
+        });
+        Ok(())
+    }
+}
+
+// A writer with more than one owner, so captured output can be read back afterwards
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// A renderer and the writer it renders to
+struct Sink {
+    renderer: Box<dyn Renderer>,
+    out: Box<dyn Write>,
+    // The first write that failed, after which nothing more is written here. Chapters can't do
+    // anything about it, so it waits for finish().
+    error: Option<io::Error>,
+}
+
+impl Sink {
+    fn new(renderer: Box<dyn Renderer>, out: Box<dyn Write>) -> Sink {
+        Sink {
+            renderer,
+            out,
+            error: None,
+        }
+    }
+
+    fn check(&mut self, result: io::Result<()>) {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        if self.error.is_none() {
+            let result = self.renderer.event(&mut self.out, event);
+            self.check(result);
+        }
+    }
+
+    // Flush, or at the end finish, the renderer and then the writer
+    fn end(&mut self, finish: bool) {
+        if self.error.is_none() {
+            let out = &mut self.out;
+            let ended = if finish { self.renderer.finish(out) } else { self.renderer.flush(out) };
+            let result = ended.and_then(|()| out.flush());
+            self.check(result);
+        }
+    }
+}
+
+// Where chapter output goes: usually stdout, and maybe a file for --tee as well
+struct Context {
+    out: Sink,
+    // Somewhere else everything goes too, with its own renderer. It carries on if `out` fails,
+    // e.g. when stdout is piped to `head`.
+    tee: Option<Sink>,
+    notes: Notes,
+    // Output printed with output_inline! that's still waiting for the end of its line
+    partial: String,
+}
+
+impl Context {
+    fn new(renderer: Box<dyn Renderer>, out: Box<dyn Write>, notes: Notes) -> Context {
+        Context {
+            out: Sink::new(renderer, out),
+            tee: None,
+            notes,
+            partial: String::new(),
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        self.out.event(event);
+        if let Some(tee) = &mut self.tee {
+            tee.event(event);
+        }
+    }
+
+    fn end(&mut self, finish: bool) {
+        self.out.end(finish);
+        if let Some(tee) = &mut self.tee {
+            tee.end(finish);
+        }
+    }
+
+    // A line started with output_inline! is output, so whatever finishes it is output too.
+    // A note is about a whole line, so it waits for the line to be done.
+    fn emit(&mut self, event: Event) {
+        if let (Event::Note(_), Notes::Hide) = (&event, self.notes) {
+            return;
+        }
+        let partial = mem::take(&mut self.partial);
+        match event {
+            _ if partial.is_empty() => self.event(event),
+            Event::Title(..) | Event::Note(_) => {
+                self.event(Event::Output(&partial));
+                self.event(event);
+            }
+            Event::Narration(text) | Event::Listing(_, text) | Event::Output(text) => {
+                self.event(Event::Output(&(partial + text)))
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        let partial = mem::take(&mut self.partial);
+        if !partial.is_empty() {
+            self.event(Event::Output(&partial));
+        }
+        self.end(false);
+    }
+
+    // The tee's error comes first: stdout closed early by `| head` is nothing to worry about,
+    // but a file that wasn't all written is
+    fn finish(&mut self) -> io::Result<()> {
+        self.flush();
+        self.end(true);
+        let tee = self.tee.as_mut().and_then(|tee| tee.error.take());
+        tee.or_else(|| self.out.error.take()).map_or(Ok(()), Err)
+    }
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> =
+        RefCell::new(Context::new(
+            Format::Plain.renderer(Notes::Dim, Theme::Ascii, None),
+            Box::new(io::stdout()),
+            Notes::Dim,
+        ));
+}
+
+// Narration in the text formats is wrapped to fit the terminal, if there is one
+pub fn set_format(format: Format, notes: Notes, theme: Theme) {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        context.out.renderer = format.renderer(notes, theme, helpers::terminal_width());
+        context.notes = notes;
+    });
+}
+
+// Send chapter output to `out` as well as stdout, e.g. a file for --tee. Files aren't terminals,
+// so it's never wrapped and ansi is written as plain.
+pub fn set_tee(format: Format, notes: Notes, theme: Theme, out: Box<dyn Write>) {
+    let format = match format {
+        Format::Ansi => Format::Plain,
+        format => format,
+    };
+    let tee = Sink::new(format.renderer(notes, theme, None), out);
+    CONTEXT.with(|context| context.borrow_mut().tee = Some(tee));
+}
+
+// Send chapter output to `out` instead of stdout, in the format already chosen
+pub fn set_output(out: Box<dyn Write>) {
+    CONTEXT.with(|context| context.borrow_mut().out.out = out);
+}
+
+// Run `run` with its output going to `context`, then put the previous context back
+// and hand `context` back to the caller
+fn with_context(context: Context, run: impl FnOnce()) -> Context {
+    let previous = CONTEXT.with(|current| current.replace(context));
+    run();
+    CONTEXT.with(|current| current.replace(previous))
+}
+
+// Run something that renders, e.g. a section, with its output going to `out` in `format`, and
+// report the first write that failed. Pass io::sink() as the writer to throw everything away.
+pub fn render_to(format: Format, out: Box<dyn Write>, run: impl FnOnce()) -> io::Result<()> {
+    let context = Context::new(
+        format.renderer(Notes::Inline, Theme::Ascii, None),
+        out,
+        Notes::Inline,
+    );
+    with_context(context, run).finish()
+}
+
+// Run something that renders and return what it would have printed
+pub fn capture(format: Format, run: impl FnOnce()) -> String {
+    let buffer = Buffer::default();
+    // Writing to memory can't fail
+    let _ = render_to(format, Box::new(buffer.clone()), run);
+    let bytes = buffer.0.take();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// Run something that renders and keep its events instead of printing them
+pub fn record(run: impl FnOnce()) -> Vec<Recorded> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorder = Box::new(Recorder {
+        events: Rc::clone(&events),
+    });
+    let context = Context::new(recorder, Box::new(io::sink()), Notes::Inline);
+    with_context(context, run).flush();
+    events.take()
+}
+
+pub fn title(level: Level, text: &str) {
+    CONTEXT.with(|context| context.borrow_mut().emit(Event::Title(level, text)));
+}
+
+pub fn write_narration(text: &str) {
+    CONTEXT.with(|context| context.borrow_mut().emit(Event::Narration(text)));
+}
+
+pub fn write_listing(kind: Code, code: &str) {
+    CONTEXT.with(|context| context.borrow_mut().emit(Event::Listing(kind, code)));
+}
+
+// List the code inside the `show_and_run!` call on `line` of a chapter's `source`, after the
+// `SOURCE;` it starts with
+pub fn write_source(source: &str, line: u32) {
+    let code = source::macro_body(source, "show_and_run", line)
+        .and_then(|code| code.split_once(';'))
+        .map(|(_, code)| code)
+        .unwrap_or_else(|| panic!("no show_and_run! on line {}", line));
+    write_listing(Code::Ran, &source::dedent(source::unwrap_block(code)));
+}
+
+pub fn write_output(text: &str) {
+    CONTEXT.with(|context| context.borrow_mut().emit(Event::Output(text)));
+}
+
+// Like print!, output that doesn't end the line. Each complete line becomes its own event.
+pub fn write_output_inline(text: &str) {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        context.partial.push_str(text);
+        if let Some(index) = context.partial.rfind('\n') {
+            let complete: String = context.partial.drain(..=index).collect();
+            for line in complete.lines() {
+                context.event(Event::Output(line));
+            }
+        }
+    });
+}
+
+pub fn write_note(text: &str) {
+    CONTEXT.with(|context| context.borrow_mut().emit(Event::Note(text)));
+}
+
+pub fn flush() {
+    CONTEXT.with(|context| context.borrow_mut().flush());
+}
+
+// Flush everything and report the first write that failed, if any did
+pub fn finish() -> io::Result<()> {
+    CONTEXT.with(|context| context.borrow_mut().finish())
+}
+
+// These work like println! (and print! for output_inline!) but send the text to the renderer
+// as the matching kind of event
+
+macro_rules! narrate {
+    () => {
+        $crate::render::write_narration("")
+    };
+    ($($arg:tt)*) => {
+        $crate::render::write_narration(&format!($($arg)*))
+    };
+}
+pub(crate) use narrate;
+
+// listing!("...") is Rust that compiles. Anything else starts with what it is, like a code
+// block in a doc comment: listing!(compile_fail; "...") for Rust that's meant not to compile,
+// listing!(ignore; "...") for an outline, or listing!(toml; "...").
+macro_rules! listing {
+    (compile_fail; $($arg:tt)*) => {
+        $crate::render::write_listing($crate::render::Code::CompileFail, &format!($($arg)*))
+    };
+    (ignore; $($arg:tt)*) => {
+        $crate::render::write_listing($crate::render::Code::Outline, &format!($($arg)*))
+    };
+    ($language:ident; $($arg:tt)*) => {
+        $crate::render::write_listing(
+            $crate::render::Code::Other(stringify!($language)),
+            &format!($($arg)*),
+        )
+    };
+    ($($arg:tt)*) => {
+        $crate::render::write_listing($crate::render::Code::Rust, &format!($($arg)*))
+    };
+}
+pub(crate) use listing;
+
+macro_rules! output {
+    ($($arg:tt)*) => {
+        $crate::render::write_output(&format!($($arg)*))
+    };
+}
+pub(crate) use output;
+
+// List code exactly as it's written in the chapter, then run it, so the listing can't say
+// something different from what ran. The chapter passes in its own source first, e.g.
+// `show_and_run! { SOURCE; ... }` with `const SOURCE: &str = include_str!("a09_functions.rs");`.
+// A single block, e.g. `show_and_run!(SOURCE; { ... })`, gives back its value; anything else runs
+// in place, and what it defines can be used afterwards.
+macro_rules! show_and_run {
+    ($source:ident; $code:block) => {{
+        $crate::render::write_source($source, line!());
+        $code
+    }};
+    ($source:ident; $($code:tt)*) => {
+        $crate::render::write_source($source, line!());
+        $($code)*
+    };
+}
+pub(crate) use show_and_run;
+
+macro_rules! note {
+    ($($arg:tt)*) => {
+        $crate::render::write_note(&format!($($arg)*))
+    };
+}
+pub(crate) use note;
+
+macro_rules! output_inline {
+    ($($arg:tt)*) => {
+        $crate::render::write_output_inline(&format!($($arg)*))
+    };
+}
+pub(crate) use output_inline;
@@ -0,0 +1,120 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How much of each of a program's streams is kept. A loop that prints forever could otherwise
+// fill memory before its time limit is up.
+pub const OUTPUT_LIMIT: usize = 1 << 20;
+
+// What a program printed, and how it finished. `status` is None when it ran past its time limit
+// and had to be killed, and `truncated` is true when either stream went past OUTPUT_LIMIT and the
+// rest was thrown away.
+pub struct Run {
+    pub stdout: String,
+    pub stderr: String,
+    pub status: Option<ExitStatus>,
+    pub truncated: bool,
+}
+
+// A fresh directory under the system temp directory to build things in, which is removed with
+// everything in it when it's dropped. It derefs to its path.
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    // Named for this process and a count as well as `name`, so no two are ever the same directory
+    pub fn new(name: &str) -> io::Result<ScratchDir> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let dir = env::temp_dir().join(format!("rust-by-example-{}-{}-{}", process::id(), count, name));
+        fs::create_dir_all(&dir)?;
+        Ok(ScratchDir(dir))
+    }
+}
+
+impl Deref for ScratchDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// Compile a single file with whichever rustc is installed, $RUSTC if it's set.
+// The output is returned whether or not it compiled, so the diagnostics can be shown.
+pub fn compile(source: &Path, binary: &Path) -> io::Result<process::Output> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    Command::new(rustc)
+        .args(["--edition", "2018", "-o"])
+        .arg(binary)
+        .arg(source)
+        .output()
+        .map_err(|error| io::Error::new(error.kind(), format!("couldn't run rustc: {}", error)))
+}
+
+// Type-check a single file without building it, like `cargo check`, with every lint allowed.
+// Whatever rustc writes goes in `dir`.
+pub fn check(source: &Path, dir: &Path) -> io::Result<process::Output> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    Command::new(rustc)
+        .args(["--edition", "2018", "--emit=metadata", "--cap-lints", "allow", "--out-dir"])
+        .arg(dir)
+        .arg(source)
+        .output()
+        .map_err(|error| io::Error::new(error.kind(), format!("couldn't run rustc: {}", error)))
+}
+
+// Up to OUTPUT_LIMIT bytes, and whether there was more. The rest is still read so the program
+// never blocks on a full pipe.
+fn read_all(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<(String, bool)> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.by_ref().take(OUTPUT_LIMIT as u64).read_to_end(&mut bytes);
+        let rest = io::copy(&mut pipe, &mut io::sink()).unwrap_or(0);
+        (String::from_utf8_lossy(&bytes).into_owned(), rest > 0)
+    })
+}
+
+// Run a compiled program, killing it if it's still going after `timeout`.
+// Its output is read on other threads so a chatty program can't fill the pipe and stall.
+pub fn run(binary: &Path, timeout: Duration) -> io::Result<Run> {
+    let mut child = Command::new(binary)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = read_all(child.stdout.take().expect("stdout is piped"));
+    let stderr = read_all(child.stderr.take().expect("stderr is piped"));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let (stdout, stdout_truncated) = stdout.join().unwrap_or_default();
+    let (stderr, stderr_truncated) = stderr.join().unwrap_or_default();
+    Ok(Run {
+        stdout,
+        stderr,
+        status,
+        truncated: stdout_truncated || stderr_truncated,
+    })
+}
@@ -0,0 +1,128 @@
+use super::chapter::CHAPTERS;
+use super::render::{self, Format};
+use std::cmp::Reverse;
+use std::io::{self, IsTerminal};
+
+// A title match counts for more than a mention somewhere in the narration
+const TITLE_WEIGHT: usize = 5;
+const EXCERPT_WIDTH: usize = 80;
+
+pub struct Hit {
+    // Something that can be passed to `run`, e.g. `8/while_let`
+    pub reference: String,
+    pub title: String,
+    pub score: usize,
+    pub excerpt: String,
+}
+
+// Byte offsets of every case-insensitive match of `term` in `text`.
+// Only ASCII letters are folded, so the offsets are valid in the original text.
+fn matches(text: &str, term: &str) -> Vec<usize> {
+    let text = text.to_ascii_lowercase();
+    let term = term.to_ascii_lowercase();
+    text.match_indices(&term).map(|(index, _)| index).collect()
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+// The first line of narration that mentions the term, cut down to roughly EXCERPT_WIDTH around
+// the first match. Falls back to the title when that's the only mention.
+fn excerpt(title: &str, text: &str, term: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| *line != title && !line.starts_with('|'))
+        .find(|line| !matches(line, term).is_empty())
+        .unwrap_or(title);
+    if line.len() <= EXCERPT_WIDTH {
+        return line.to_string();
+    }
+    let first = matches(line, term)[0];
+    let start = floor_char_boundary(line, first.saturating_sub(EXCERPT_WIDTH / 3));
+    let end = floor_char_boundary(line, (start + EXCERPT_WIDTH).min(line.len()));
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        &line[start..end],
+        if end < line.len() { "..." } else { "" }
+    )
+}
+
+fn score(title: &str, text: &str, term: &str) -> usize {
+    matches(title, term).len() * TITLE_WEIGHT + matches(text, term).len()
+}
+
+// Search every chapter intro and section for `term`, best matches first
+pub fn search(term: &str) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for chapter in CHAPTERS.iter() {
+        let mut pages = vec![(
+            chapter.number().to_string(),
+            chapter.title(),
+            None,
+            render::capture(Format::Plain, || chapter.show_intro()),
+        )];
+        for (depth, section) in chapter.outline() {
+            pages.push((
+                format!("{}/{}", chapter.number(), section.slug),
+                section.title,
+                Some(section.title),
+                render::capture(Format::Plain, || section.show(depth)),
+            ));
+        }
+
+        for (reference, title, section_title, text) in pages {
+            let score = score(title, &text, term);
+            if score > 0 {
+                hits.push(Hit {
+                    excerpt: excerpt(title, &text, term),
+                    title: match section_title {
+                        Some(section_title) => format!("{}: {}", chapter.title(), section_title),
+                        None => chapter.title().to_string(),
+                    },
+                    reference,
+                    score,
+                });
+            }
+        }
+    }
+    // The sort is stable, so equal scores stay in course order
+    hits.sort_by_key(|hit| Reverse(hit.score));
+    hits
+}
+
+// Mark every match, in reverse video on a terminal or with [brackets] when piped somewhere
+fn highlight(text: &str, term: &str) -> String {
+    let (open, close) = if io::stdout().is_terminal() {
+        ("\x1b[7m", "\x1b[0m")
+    } else {
+        ("[", "]")
+    };
+    let mut highlighted = String::new();
+    let mut last = 0;
+    for index in matches(text, term) {
+        highlighted.push_str(&text[last..index]);
+        highlighted.push_str(open);
+        highlighted.push_str(&text[index..index + term.len()]);
+        highlighted.push_str(close);
+        last = index + term.len();
+    }
+    highlighted.push_str(&text[last..]);
+    highlighted
+}
+
+pub fn print(hits: &[Hit], term: &str) {
+    if hits.is_empty() {
+        println!("Nothing mentions \"{}\"", term);
+        return;
+    }
+    for hit in hits {
+        println!("{:<28} {} ({})", hit.reference, hit.title, hit.score);
+        println!("\t{}", highlight(&hit.excerpt, term));
+    }
+}
@@ -0,0 +1,248 @@
+use super::chapter::{self, CHAPTERS};
+use super::diagnostics;
+use super::site;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::SystemTime;
+
+// The course as a website on localhost. Nothing is cached: every page is made by running its
+// chapter or section again when it's asked for, in a program of its own started for that request.
+// When serve is run from the repo, the course is rebuilt first if it's been edited, so that
+// program is always the code as it is now.
+
+// What the course is built from, relative to the repo
+const SOURCES: [&str; 4] = ["Cargo.toml", "src", "rary", "exercises"];
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn html(body: String) -> Response {
+        Response {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Response {
+        Response {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: format!("{}\n", message),
+        }
+    }
+}
+
+// The static site's pages link to each other relative to the root, so every page says that's
+// where its links start, even the ones further down like /8/match_guards
+fn rooted(html: String) -> String {
+    html.replacen("<head>\n", "<head>\n<base href=\"/\">\n", 1)
+}
+
+// `/` and the static site's file names, plus `/<chapter>` and `/<chapter>/<section>`
+// the way `run` takes them, e.g. `/8/match_guards` or `/flow-control`
+fn respond(path: &str) -> Response {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    match path.trim_start_matches('/') {
+        "" | "index.html" => Response::html(rooted(site::index_page())),
+        "style.css" => Response {
+            status: "200 OK",
+            content_type: "text/css; charset=utf-8",
+            body: site::STYLE.to_string(),
+        },
+        name => {
+            let page = CHAPTERS.iter().find(|&&chapter| site::page_name(chapter) == name);
+            if let Some(&chapter) = page {
+                return Response::html(rooted(site::chapter_page(chapter)));
+            }
+            match chapter::lookup(name.trim_end_matches('/')) {
+                Ok((chapter, Some((depth, section)))) => {
+                    Response::html(rooted(site::section_page(chapter, depth, section)))
+                }
+                Ok((chapter, None)) => Response::html(rooted(site::chapter_page(chapter))),
+                Err(message) => Response::error("404 Not Found", &message),
+            }
+        }
+    }
+}
+
+fn send(response: &Response, method: &str, out: &mut impl Write) -> io::Result<()> {
+    write!(
+        out,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    if method != "HEAD" {
+        out.write_all(response.body.as_bytes())?;
+    }
+    out.flush()
+}
+
+// Write the whole response to a request for `path` to stdout. This is what serve runs for every
+// request, as `serve-page <method> <path>`, so the page comes from the latest build.
+pub fn page(method: &str, path: &str) -> io::Result<()> {
+    send(&respond(path), method, &mut io::stdout().lock())
+}
+
+// The latest modification time of `path`, or anything in it if it's a directory
+fn last_modified(path: &Path) -> SystemTime {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return SystemTime::UNIX_EPOCH,
+    };
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if !metadata.is_dir() {
+        return modified;
+    }
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| last_modified(&entry.path()))
+        .fold(modified, SystemTime::max)
+}
+
+struct Build {
+    // This program, which cargo replaces when it rebuilds the course
+    program: PathBuf,
+    // The repo it was built from, when it's running from that repo's target directory
+    repo: Option<PathBuf>,
+    // So two requests don't both start a build
+    building: Mutex<()>,
+}
+
+impl Build {
+    fn new() -> io::Result<Build> {
+        let program = env::current_exe()?;
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR"));
+        Ok(Build {
+            repo: Some(repo.to_path_buf()).filter(|repo| program.starts_with(repo)),
+            program,
+            building: Mutex::new(()),
+        })
+    }
+
+    // Rebuild the course if anything it's built from has changed since this program was built.
+    // Err is what cargo said when it doesn't build.
+    fn rebuild(&self) -> Result<(), String> {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return Ok(()),
+        };
+        let _building = self.building.lock().unwrap_or_else(PoisonError::into_inner);
+        let built = last_modified(&self.program);
+        if SOURCES.iter().all(|source| last_modified(&repo.join(source)) <= built) {
+            return Ok(());
+        }
+        let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+        cargo
+            .args(["build", "--quiet", "--bin", env!("CARGO_PKG_NAME")])
+            .current_dir(repo);
+        if !cfg!(debug_assertions) {
+            cargo.arg("--release");
+        }
+        let output = cargo
+            .output()
+            .map_err(|error| format!("Couldn't run cargo: {}", error))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into_owned())
+        }
+    }
+
+    // The whole response to a request for `path`, from the course as it is now
+    fn page(&self, method: &str, path: &str) -> Result<Vec<u8>, Response> {
+        let failed = |message: String| Response::error("500 Internal Server Error", &message);
+        self.rebuild()
+            .map_err(|errors| failed(format!("The course doesn't build:\n\n{}", errors)))?;
+        let mut page = Command::new(&self.program);
+        if diagnostics::compiling() {
+            page.arg("--compile-errors");
+        }
+        let output = page
+            .args(["serve-page", method, path])
+            .output()
+            .map_err(|error| failed(format!("Couldn't make the page: {}", error)))?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(failed(String::from_utf8_lossy(&output.stderr).into_owned()))
+        }
+    }
+}
+
+// Read one request and answer it. Only GET and HEAD make sense for pages, and the connection is
+// closed after every response, which keeps this simple and is all a browser needs.
+fn handle(stream: TcpStream, build: &Build) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // The headers don't matter, but they have to be read before answering
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or("/"));
+    let mut stream = stream;
+    let response = match method {
+        // The path is passed on as an argument, so it mustn't look like an option
+        _ if !path.starts_with('/') => Response::error("400 Bad Request", "Paths start with /"),
+        "GET" | "HEAD" => match build.page(method, path) {
+            Ok(page) => {
+                stream.write_all(&page)?;
+                return stream.flush();
+            }
+            Err(response) => response,
+        },
+        _ => Response::error("405 Method Not Allowed", "Only GET and HEAD are supported"),
+    };
+    send(&response, method, &mut stream)
+}
+
+// Serve the course on localhost at `port` until the process is stopped. Port 0 picks a free one,
+// and the address is printed either way.
+pub fn serve(port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let build = Arc::new(Build::new()?);
+    let rebuilt = match &build.repo {
+        Some(repo) => format!(" (rebuilt from {} when it's edited)", repo.display()),
+        None => String::new(),
+    };
+    println!(
+        "Serving the course on http://{}/{} - press Ctrl+C to stop",
+        listener.local_addr()?,
+        rebuilt
+    );
+    io::stdout().flush()?;
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Couldn't accept a connection: {}", error);
+                continue;
+            }
+        };
+        let build = Arc::clone(&build);
+        thread::spawn(move || {
+            if let Err(error) = handle(stream, &build) {
+                eprintln!("Couldn't answer a request: {}", error);
+            }
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use super::chapter::{Chapter, Section, CHAPTERS};
+use super::highlight;
+use super::render::{self, escape_html, Code, Level, Recorded};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Everything the pages need is in this one file, so the site works straight off the disk
+pub const STYLE: &str = "body {
+    max-width: 70rem;
+    margin: 0 auto;
+    padding: 0 1rem 2rem;
+    font-family: sans-serif;
+    line-height: 1.5;
+    color: #222;
+}
+nav {
+    display: flex;
+    justify-content: space-between;
+    padding: 1rem 0;
+    border-bottom: 1px solid #ddd;
+}
+nav.bottom {
+    border-top: 1px solid #ddd;
+    border-bottom: none;
+    margin-top: 2rem;
+}
+a {
+    color: #a72145;
+}
+h2 {
+    margin-top: 2.5rem;
+    border-bottom: 2px solid #a72145;
+}
+pre {
+    margin: 0.5rem 0;
+    padding: 0.75rem;
+    overflow-x: auto;
+    tab-size: 4;
+    border-radius: 4px;
+}
+pre.listing {
+    background: #f6f8fa;
+    border: 1px solid #e1e4e8;
+}
+pre.output {
+    background: #1e1e1e;
+    color: #d4d4d4;
+}
+.keyword {
+    color: #a72145;
+    font-weight: bold;
+}
+.literal {
+    color: #005cc5;
+}
+.string {
+    color: #22863a;
+}
+.lifetime {
+    color: #6f42c1;
+    font-style: italic;
+}
+.macro {
+    color: #6f42c1;
+}
+.comment {
+    color: #6a737d;
+    font-style: italic;
+}
+.example {
+    display: grid;
+    grid-template-columns: 1fr 1fr;
+    gap: 1rem;
+    align-items: start;
+}
+li.subsection {
+    margin-left: 1.5rem;
+}
+pre.note {
+    padding: 0 0.75rem;
+    color: #666;
+    font-size: 0.9em;
+}
+.example pre {
+    margin: 0;
+}
+@media (max-width: 50rem) {
+    .example {
+        grid-template-columns: 1fr;
+    }
+}
+";
+
+// e.g. `03-custom-types.html`
+pub fn page_name(chapter: &dyn Chapter) -> String {
+    format!("{:02}-{}.html", chapter.number(), chapter.slug())
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{}</title>
+<link rel=\"stylesheet\" href=\"style.css\">
+</head>
+<body>
+{}</body>
+</html>
+",
+        escape_html(title),
+        body
+    )
+}
+
+// Blank lines the chapters print to space things out on a terminal aren't needed inside a box
+fn pre(class: &str, lines: &[&str]) -> String {
+    format!(
+        "<pre class=\"{}\"><samp>{}</samp></pre>\n",
+        class,
+        escape_html(lines.join("\n").trim_matches('\n'))
+    )
+}
+
+// Listings printed one after another, with the Rust ones highlighted
+fn listing(listings: &[(Code, &str)]) -> String {
+    let code: Vec<String> = listings
+        .iter()
+        .map(|&(kind, code)| match kind.language() {
+            "rust" => highlight::html(code.trim_matches('\n')),
+            _ => escape_html(code.trim_matches('\n')),
+        })
+        .collect();
+    format!("<pre class=\"listing\"><code>{}</code></pre>\n", code.join("\n"))
+}
+
+// Lay out a page's narration as paragraphs, and each code listing side by side with the
+// output that comes straight after it. Titles get `anchor` as their id, so they can be linked to.
+fn body(events: &[Recorded], anchor: &str) -> String {
+    let mut html = String::new();
+    let mut index = 0;
+    while index < events.len() {
+        let mut listings = Vec::new();
+        while let Some(Recorded::Listing(kind, code)) = events.get(index) {
+            listings.push((*kind, code.as_str()));
+            index += 1;
+        }
+        let mut output = Vec::new();
+        while let Some(Recorded::Output(text)) = events.get(index) {
+            output.push(text.as_str());
+            index += 1;
+        }
+        match (listings.is_empty(), output.is_empty()) {
+            (false, false) => {
+                html.push_str("<div class=\"example\">\n");
+                html.push_str(&listing(&listings));
+                html.push_str(&pre("output", &output));
+                html.push_str("</div>\n");
+            }
+            (false, true) => html.push_str(&listing(&listings)),
+            (true, false) => html.push_str(&pre("output", &output)),
+            (true, true) => {
+                match &events[index] {
+                    Recorded::Title(level, title) => {
+                        let tag = match level {
+                            Level::Example => "h1",
+                            Level::Section => "h2",
+                            Level::Subsection => "h3",
+                        };
+                        html.push_str(&format!(
+                            "<{0} id=\"{1}\">{2}</{0}>\n",
+                            tag,
+                            anchor,
+                            escape_html(title)
+                        ));
+                    }
+                    Recorded::Narration(text) if !text.trim().is_empty() => {
+                        html.push_str(&format!("<p>{}</p>\n", escape_html(text.trim())));
+                    }
+                    Recorded::Note(text) => html.push_str(&pre("note", &[text])),
+                    _ => {}
+                }
+                index += 1;
+            }
+        }
+    }
+    html
+}
+
+fn chapter_link(chapter: &dyn Chapter, label: String) -> String {
+    format!("<a href=\"{}\">{}</a>", page_name(chapter), label)
+}
+
+// Links to the contents and to the chapters either side of the one at `index` in CHAPTERS
+fn nav(class: &str, index: usize) -> String {
+    let previous = match index.checked_sub(1).and_then(|index| CHAPTERS.get(index)) {
+        Some(&chapter) => chapter_link(chapter, format!("&larr; {}", escape_html(chapter.title()))),
+        None => "<span></span>".to_string(),
+    };
+    let next = match CHAPTERS.get(index + 1) {
+        Some(&chapter) => chapter_link(chapter, format!("{} &rarr;", escape_html(chapter.title()))),
+        None => "<span></span>".to_string(),
+    };
+    format!(
+        "<nav class=\"{}\">{}<a href=\"index.html\">Contents</a>{}</nav>\n",
+        class, previous, next
+    )
+}
+
+pub fn chapter_page(chapter: &dyn Chapter) -> String {
+    let position = chapter.number() - 1;
+    let title = format!("Example {}: {}", chapter.number(), chapter.title());
+    let mut html = nav("top", position);
+    html.push_str(&body(&render::record(|| chapter.show_intro()), chapter.slug()));
+    // Each section on its own, since its subsections come round in the outline
+    for (depth, section) in chapter.outline() {
+        html.push_str(&body(&render::record(|| section.show(depth)), section.slug));
+    }
+    html.push_str(&nav("bottom", position));
+    page(&title, &html)
+}
+
+// Just one section, between the same links as its chapter's page
+pub fn section_page(chapter: &dyn Chapter, depth: usize, section: &Section) -> String {
+    let position = chapter.number() - 1;
+    let title = format!("{} - Example {}: {}", section.title, chapter.number(), chapter.title());
+    let mut html = nav("top", position);
+    html.push_str(&body(&render::record(|| section.show(depth)), section.slug));
+    html.push_str(&nav("bottom", position));
+    page(&title, &html)
+}
+
+pub fn index_page() -> String {
+    let mut html = String::from("<h1>Rust by Example</h1>\n<ol>\n");
+    for &chapter in CHAPTERS.iter() {
+        let name = page_name(chapter);
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>\n<ul>\n",
+            name,
+            escape_html(chapter.title())
+        ));
+        for (depth, section) in chapter.outline() {
+            html.push_str(&format!(
+                "<li{}><a href=\"{}#{}\">{}</a></li>\n",
+                if depth > 0 { " class=\"subsection\"" } else { "" },
+                name,
+                section.slug,
+                escape_html(section.title)
+            ));
+        }
+        html.push_str("</ul>\n</li>\n");
+    }
+    html.push_str("</ol>\n");
+    page("Rust by Example", &html)
+}
+
+// Write the whole course as a static site in `dir`: index.html, one page per chapter, and style.css
+pub fn build(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("style.css"), STYLE)?;
+    fs::write(dir.join("index.html"), index_page())?;
+    for &chapter in CHAPTERS.iter() {
+        fs::write(dir.join(page_name(chapter)), chapter_page(chapter))?;
+    }
+    println!(
+        "Wrote {} chapters to {} - open {} in a browser",
+        CHAPTERS.len(),
+        dir.display(),
+        dir.join("index.html").display()
+    );
+    Ok(())
+}
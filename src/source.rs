@@ -0,0 +1,140 @@
+// Pulling code back out of the chapters' own source files, so what's shown is what runs
+
+// Skip over a string, char or comment starting at `index`, returning where it ends.
+// Anything else is returned as is, one character on.
+pub fn skip_literal(source: &str, index: usize) -> usize {
+    let code = source.as_bytes();
+    let rest = &code[index..];
+    let find = |pattern: &[u8], from: usize| {
+        (from..code.len())
+            .find(|&at| code[at..].starts_with(pattern))
+            .map_or(code.len(), |at| at + pattern.len())
+    };
+    // Whether `at` isn't partway through an identifier, like the r in `for`
+    let starts_word =
+        |at: usize| at == 0 || !(code[at - 1].is_ascii_alphanumeric() || code[at - 1] == b'_');
+    match rest {
+        [b'/', b'/', ..] => find(b"\n", index),
+        [b'/', b'*', ..] => find(b"*/", index + 2),
+        [b'"', ..] => {
+            let mut at = index + 1;
+            while at < code.len() && code[at] != b'"' {
+                at += if code[at] == b'\\' { 2 } else { 1 };
+            }
+            at + 1
+        }
+        // A raw string, e.g. r#"..."#, ends at the quote followed by as many #s as it started with.
+        // Byte raw strings, br"...", start the same way after the b.
+        [b'r', b'"', ..] | [b'r', b'#', ..]
+            if starts_word(index) || (code[index - 1] == b'b' && starts_word(index - 1)) =>
+        {
+            let hashes = rest[1..].iter().take_while(|&&byte| byte == b'#').count();
+            if rest.get(1 + hashes) != Some(&b'"') {
+                return index + 1;
+            }
+            let mut end = vec![b'"'];
+            end.extend(std::iter::repeat_n(b'#', hashes));
+            find(&end, index + 2 + hashes)
+        }
+        // 'a' and '\n' are chars, but 'a on its own is a lifetime or label
+        [b'\'', b'\\', ..] => find(b"'", index + 3),
+        [b'\'', ..] => {
+            let width = source[index + 1..].chars().next().map_or(1, char::len_utf8);
+            if rest.get(1 + width) == Some(&b'\'') {
+                index + 2 + width
+            } else {
+                index + 1
+            }
+        }
+        _ => index + 1,
+    }
+}
+
+// Where the bracket at `open` is closed, skipping over any in strings, chars and comments
+fn closing(source: &str, open: usize) -> Option<usize> {
+    let code = source.as_bytes();
+    let (open_byte, close_byte) = match code[open] {
+        b'(' => (b'(', b')'),
+        b'{' => (b'{', b'}'),
+        b'[' => (b'[', b']'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    let mut index = open;
+    while index < code.len() {
+        match code[index] {
+            byte if byte == open_byte => depth += 1,
+            byte if byte == close_byte => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+        index = skip_literal(source, index);
+    }
+    None
+}
+
+// The code between the brackets of the first `name!(...)`, `name! {...}` or `name![...]`
+// at or after line `line` (counting from 1), without the brackets
+pub fn macro_body<'a>(source: &'a str, name: &str, line: u32) -> Option<&'a str> {
+    let start = source
+        .split_inclusive('\n')
+        .take(line as usize - 1)
+        .map(str::len)
+        .sum::<usize>();
+    let call = format!("{}!", name);
+    let after = start + source[start..].find(&call)? + call.len();
+    let open = after + source[after..].find(|c: char| !c.is_whitespace())?;
+    Some(&source[open + 1..closing(source, open)?])
+}
+
+// The inside of `code` if it's nothing but one { ... } block, otherwise `code` itself
+pub fn unwrap_block(code: &str) -> &str {
+    let trimmed = code.trim();
+    match closing(trimmed, 0) {
+        Some(end) if trimmed.starts_with('{') && end == trimmed.len() - 1 => &trimmed[1..end],
+        _ => code,
+    }
+}
+
+// Code as it should be listed: without the blank lines around it or the indentation it
+// had inside the function it was taken from, and indented with tabs like the other listings
+pub fn dedent(code: &str) -> String {
+    let lines: Vec<&str> = code
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(0, |end| end + 1);
+    let lines = &lines[..end];
+    // Tabs count as four spaces, so lines indented with either line up
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let code = line.trim_start_matches([' ', '\t']);
+            let spaces: usize = line[..line.len() - code.len()]
+                .chars()
+                .map(|c| if c == '\t' { 4 } else { 1 })
+                .sum();
+            format!("{}{}", " ".repeat(spaces), code)
+        })
+        .collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.get(indent..).unwrap_or("").trim_end();
+            let code = line.trim_start_matches(' ');
+            let spaces = line.len() - code.len();
+            format!("{}{}{}", "\t".repeat(spaces / 4), &line[spaces / 4 * 4..spaces], code)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
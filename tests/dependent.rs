@@ -0,0 +1,44 @@
+// Builds a separate crate that depends on this one, the way another program would use the
+// library. Tests in this package can't catch paths that only work from inside it.
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const MAIN: &str = r#"fn main() {
+    let chapters = rust_by_example::chapters();
+    println!("{} chapters", chapters.len());
+    let text = rust_by_example::run_to_string("5/casting", rust_by_example::Format::Plain);
+    print!("{}", text.unwrap().text);
+}
+"#;
+
+#[test]
+fn another_crate_can_use_the_library() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("dependent");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    // Its own [workspace], since target/ is inside this one
+    let manifest = format!(
+        "[package]\nname = \"dependent\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n\
+         [dependencies]\nrust-by-example = {{ path = {:?} }}\n\n[workspace]\n",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    fs::write(dir.join("src").join("main.rs"), MAIN).unwrap();
+
+    let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["run", "--quiet", "--offline"])
+        .current_dir(&dir)
+        .output()
+        .expect("couldn't run cargo");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.starts_with("12 chapters\n"), "{}", stdout);
+    // A listing from show_and_run!, which reads the chapter's own source
+    assert!(stdout.contains("let integer = decimal as u8;"), "{}", stdout);
+}
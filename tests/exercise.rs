@@ -0,0 +1,53 @@
+// `exercise check` against answers that print far too much: the output is cut off and the diff
+// stays small instead of comparing every line with every other
+use std::env;
+use std::fs;
+use std::process::Command;
+
+fn check(name: &str, answer: &str) -> String {
+    let path = env::temp_dir().join(format!(
+        "rust-by-example-exercise-{}-{}.rs",
+        std::process::id(),
+        name
+    ));
+    fs::write(&path, answer).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .args(["exercise", "check", "1/display_color"])
+        .arg(&path)
+        .output()
+        .expect("couldn't run `exercise check`");
+    let _ = fs::remove_file(&path);
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn huge_output_is_cut_off_and_only_the_first_difference_shown() {
+    let stdout = check(
+        "huge",
+        "fn main() { for _ in 0..300_000 { println!(\"not the answer\"); } }",
+    );
+    assert!(
+        stdout.contains("so only the start of it was kept"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("- RGB (128, 255, 90) 0x80FF5A #80FF5A\n+ not the answer\n"));
+    assert!(stdout.contains("That's the first difference, at line 1."));
+    assert!(stdout.lines().count() < 20, "{}", stdout);
+}
+
+#[test]
+fn long_diffs_are_counted_rather_than_printed() {
+    let stdout = check(
+        "long",
+        "fn main() { for _ in 0..200 { println!(\"not the answer\"); } }",
+    );
+    assert!(!stdout.contains("only the start of it was kept"));
+    assert!(stdout.contains("- RGB (0, 0, 0) 0x000000 #000000\n+ not the answer\n"));
+    assert!(
+        stdout.contains("(and 153 more lines of differences)"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,408 @@
+// Exports the whole course in each form and checks what's written hangs together
+use rust_by_example::latex::{self, paragraph};
+use rust_by_example::{chapters, mdbook, notebook};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// A fresh directory to export into, under the system temp directory
+fn export_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "rust-by-example-export-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// The (text, target) of every Markdown link in `markdown`
+fn links(markdown: &str) -> Vec<(&str, &str)> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let (text, rest) = line.split_once('[')?.1.split_once("](")?;
+            Some((text, rest.strip_suffix(')')?))
+        })
+        .collect()
+}
+
+#[test]
+fn mdbook_summary_links_to_every_page() {
+    let dir = export_dir("mdbook");
+    let written = mdbook::export(&dir).unwrap();
+    assert!(written.iter().all(|path| path.is_file()));
+    assert_eq!(written[0], dir.join("book.toml"));
+
+    let summary = fs::read_to_string(dir.join("src").join("SUMMARY.md")).unwrap();
+    let links = links(&summary);
+    let sections: usize = chapters()
+        .iter()
+        .map(|chapter| chapter.sections.len())
+        .sum();
+    assert_eq!(links.len(), chapters().len() + sections, "{}", summary);
+    // and nothing but the pages, book.toml and SUMMARY.md
+    assert_eq!(written.len(), links.len() + 2);
+    for (text, target) in links {
+        let page = fs::read_to_string(dir.join("src").join(target))
+            .unwrap_or_else(|_| panic!("{} links to {}, which wasn't written", text, target));
+        // every page starts with its own title as the page's heading
+        assert!(
+            page.starts_with("# "),
+            "{} starts {:?}",
+            target,
+            page.lines().next()
+        );
+    }
+    assert!(summary.contains("- [Example 3: Custom Types](03-custom-types/index.md)\n"));
+    assert!(
+        summary.contains("    - [Structures](03-custom-types/structures.md)\n"),
+        "{}",
+        summary
+    );
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// Just enough JSON to check a notebook is well-formed and look inside it
+#[derive(Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> &Json {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value)
+                .unwrap_or_else(|| panic!("no {:?} in {:?}", key, self)),
+            _ => panic!("{:?} isn't an object", self),
+        }
+    }
+
+    fn items(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            _ => panic!("{:?} isn't an array", self),
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Json::String(text) => text,
+            _ => panic!("{:?} isn't a string", self),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    at: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.at..];
+        self.at += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.text[self.at..].starts_with(token);
+        if found {
+            self.at += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) {
+        assert!(self.eat(token), "expected {:?} at byte {}", token, self.at);
+    }
+
+    // Items separated by commas up to `close`, each read by `item`
+    fn list(&mut self, close: &str, mut item: impl FnMut(&mut Self)) {
+        if self.eat(close) {
+            return;
+        }
+        loop {
+            item(self);
+            if self.eat(close) {
+                return;
+            }
+            self.expect(",");
+        }
+    }
+
+    fn string(&mut self) -> String {
+        self.expect("\"");
+        let mut string = String::new();
+        let mut chars = self.text[self.at..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.at += index + 1;
+                    return string;
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = (0..4)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        let code = u32::from_str_radix(&hex, 16).expect("bad \\u escape");
+                        string.push(char::from_u32(code).expect("\\u escape isn't a char"));
+                    }
+                    Some(c @ ('"' | '\\' | '/')) => string.push(c),
+                    other => panic!("bad escape {:?} at byte {}", other, self.at + index),
+                },
+                c if (c as u32) < 0x20 => panic!("unescaped {:?} at byte {}", c, self.at + index),
+                c => string.push(c),
+            }
+        }
+        panic!("unterminated string at byte {}", self.at)
+    }
+
+    fn value(&mut self) -> Json {
+        self.skip_whitespace();
+        match self.text[self.at..].chars().next() {
+            Some('{') => {
+                self.at += 1;
+                let mut fields = Vec::new();
+                self.list("}", |parser| {
+                    let name = parser.string();
+                    parser.expect(":");
+                    fields.push((name, parser.value()));
+                });
+                Json::Object(fields)
+            }
+            Some('[') => {
+                self.at += 1;
+                let mut items = Vec::new();
+                self.list("]", |parser| items.push(parser.value()));
+                Json::Array(items)
+            }
+            Some('"') => Json::String(self.string()),
+            _ if self.eat("null") => Json::Null,
+            _ if self.eat("true") => Json::Bool(true),
+            _ if self.eat("false") => Json::Bool(false),
+            _ => {
+                let rest = &self.text[self.at..];
+                let length = rest
+                    .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                    .unwrap_or(rest.len());
+                let number = rest[..length].parse().unwrap_or_else(|_| {
+                    panic!(
+                        "unexpected {:?} at byte {}",
+                        &rest[..length.max(1)],
+                        self.at
+                    )
+                });
+                self.at += length;
+                Json::Number(number)
+            }
+        }
+    }
+}
+
+fn parse(text: &str) -> Json {
+    let mut parser = Parser { text, at: 0 };
+    let value = parser.value();
+    parser.skip_whitespace();
+    assert_eq!(parser.at, text.len(), "something after the JSON value");
+    value
+}
+
+// Notebooks keep text as a list of lines, each but the last ending in a newline
+fn joined(lines: &Json) -> String {
+    let lines = lines.items();
+    for line in lines.iter().take(lines.len().saturating_sub(1)) {
+        assert!(
+            line.text().ends_with('\n'),
+            "{:?} doesn't end its line",
+            line
+        );
+    }
+    lines.iter().map(Json::text).collect()
+}
+
+#[test]
+fn notebooks_are_nbformat_4() {
+    let dir = export_dir("ipynb");
+    let written = notebook::export(&dir).unwrap();
+    assert_eq!(written.len(), chapters().len());
+    let mut printed = false;
+    for (chapter, path) in chapters().into_iter().zip(written) {
+        assert_eq!(
+            path,
+            dir.join(format!("{:02}-{}.ipynb", chapter.number, chapter.slug))
+        );
+        let notebook = parse(&fs::read_to_string(&path).unwrap());
+        assert_eq!(notebook.get("nbformat"), &Json::Number(4.0));
+        assert_eq!(notebook.get("nbformat_minor"), &Json::Number(4.0));
+        let kernel = notebook.get("metadata").get("kernelspec");
+        assert_eq!(kernel.get("language").text(), "rust");
+
+        let cells = notebook.get("cells").items();
+        // The first cell defines the course's macros for the listings that use them
+        assert!(joined(cells[0].get("source")).contains("macro_rules! output"));
+        for cell in cells {
+            let source = joined(cell.get("source"));
+            assert!(!source.is_empty(), "an empty cell in {}", path.display());
+            match cell.get("cell_type").text() {
+                "markdown" => {}
+                "code" => {
+                    assert_eq!(cell.get("execution_count"), &Json::Null);
+                    // Outlines are only there to read, so they stay in the Markdown
+                    assert!(!source.contains("{ ... }"), "an outline to run: {}", source);
+                    for output in cell.get("outputs").items() {
+                        assert_eq!(output.get("name").text(), "stdout");
+                        printed |= !joined(output.get("text")).is_empty();
+                    }
+                }
+                other => panic!("a {} cell in {}", other, path.display()),
+            }
+        }
+        let title = format!("# Example {}: {}", chapter.number, chapter.title);
+        assert!(joined(cells[1].get("source")).starts_with(&title));
+    }
+    assert!(printed, "no code cell has any output");
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn the_json_checker_checks() {
+    assert_eq!(
+        parse(r#"{"a": [1, -2.5e1, "x\"\u00e9\n"], "b": null}"#),
+        Json::Object(vec![
+            (
+                "a".to_string(),
+                Json::Array(vec![
+                    Json::Number(1.0),
+                    Json::Number(-25.0),
+                    Json::String("x\"é\n".to_string())
+                ])
+            ),
+            ("b".to_string(), Json::Null)
+        ])
+    );
+    for bad in [
+        r#"{"a": 1,}"#,
+        r#"["unterminated]"#,
+        "[1] 2",
+        "{\"a\": \"tab\there\"}",
+    ] {
+        assert!(
+            std::panic::catch_unwind(|| parse(bad)).is_err(),
+            "{} parsed",
+            bad
+        );
+    }
+}
+
+#[test]
+fn latex_backticks_and_special_characters() {
+    assert_eq!(
+        paragraph("use `impl Trait` here"),
+        r"use \texttt{impl Trait} here"
+    );
+    // an odd backtick out doesn't start code that never ends
+    assert_eq!(
+        paragraph("`a` and a stray ` b"),
+        r"\texttt{a} and a stray \textasciigrave{} b"
+    );
+    assert_eq!(
+        paragraph(r"50% of $x_1 & #{y} \ ~^"),
+        r"50\% of \$x\_1 \& \#\{y\} \textbackslash{} \textasciitilde{}\textasciicircum{}"
+    );
+    assert_eq!(paragraph("`{:?}`"), r"\texttt{\{:?\}}");
+}
+
+// Outside of listings, which are set as they are, every { is closed and every \begin ended
+fn check_balanced(name: &str, latex: &str) {
+    let mut depth = 0i32;
+    let mut environments = Vec::new();
+    let mut verbatim = false;
+    for line in latex.lines() {
+        if verbatim {
+            verbatim = line != r"\end{Verbatim}";
+            continue;
+        }
+        if line.starts_with(r"\begin{Verbatim}") {
+            verbatim = true;
+            continue;
+        }
+        if let Some(environment) = line.strip_prefix(r"\begin{") {
+            environments.push(environment.split('}').next().unwrap_or_default());
+        }
+        if let Some(environment) = line.strip_prefix(r"\end{") {
+            let environment = environment.split('}').next().unwrap_or_default();
+            assert_eq!(environments.pop(), Some(environment), "{}: {}", name, line);
+        }
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            assert!(depth >= 0, "{}: a }} that closes nothing in {}", name, line);
+        }
+    }
+    assert!(!verbatim, "{}: a Verbatim that doesn't end", name);
+    assert_eq!(depth, 0, "{}: a {{ that isn't closed", name);
+    assert!(
+        environments.is_empty(),
+        "{}: {:?} not ended",
+        name,
+        environments
+    );
+}
+
+#[test]
+fn latex_main_includes_every_chapter() {
+    let dir = export_dir("latex");
+    let written = latex::export(&dir).unwrap();
+    assert_eq!(written.len(), chapters().len() + 1);
+    assert_eq!(written.last(), Some(&dir.join("main.tex")));
+    let main = fs::read_to_string(dir.join("main.tex")).unwrap();
+    check_balanced("main.tex", &main);
+    let includes: Vec<&str> = main
+        .lines()
+        .filter_map(|line| line.strip_prefix(r"\include{")?.strip_suffix('}'))
+        .collect();
+    let expected: Vec<String> = chapters()
+        .iter()
+        .map(|chapter| format!("chapters/{:02}-{}", chapter.number, chapter.slug))
+        .collect();
+    assert_eq!(includes, expected);
+    assert!(main.trim_end().ends_with(r"\end{document}"));
+
+    for (include, chapter) in includes.iter().zip(chapters()) {
+        let path = dir.join(format!("{}.tex", include));
+        let latex = fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("main.tex includes {}, which wasn't written", include));
+        check_balanced(include, &latex);
+        let title = format!(r"\chapter{{{}}}", chapter.title);
+        assert!(
+            latex.starts_with(&title),
+            "{} starts {:?}",
+            include,
+            latex.lines().next()
+        );
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
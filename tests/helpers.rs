@@ -0,0 +1,69 @@
+// Measuring text in terminal columns, for titles and wrapping narration
+use rust_by_example::helpers::{centre, example_title, width, wrap};
+use rust_by_example::render::Theme;
+
+#[test]
+fn width_counts_columns_not_bytes() {
+    assert_eq!(width("plain"), 5);
+    assert_eq!(width("├──"), 3);
+    // CJK characters take up two columns
+    assert_eq!(width("漢字"), 4);
+    // and combining accents none, so é written as e and an accent is one
+    assert_eq!(width("e\u{301}"), 1);
+    assert_eq!(width("caf\u{e9}"), 4);
+}
+
+#[test]
+fn centre_pads_by_width() {
+    assert_eq!(centre("漢字", 8), "  漢字  ");
+    assert_eq!(centre("odd", 6), " odd  ");
+    // and doesn't cut anything too wide to fit
+    assert_eq!(centre("too wide", 4), "too wide");
+}
+
+#[test]
+fn titles_line_up_whatever_they_are_written_in() {
+    let mut out = Vec::new();
+    example_title(&mut out, Theme::Ascii, "Example 1: 漢字 e\u{301}").unwrap();
+    let title = String::from_utf8(out).unwrap();
+    let widths: Vec<usize> = title
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(width)
+        .collect();
+    assert!(
+        widths.windows(2).all(|pair| pair[0] == pair[1]),
+        "{}",
+        title
+    );
+}
+
+#[test]
+fn wrap_keeps_the_indentation() {
+    assert_eq!(
+        wrap("\tA tab counts as eight columns here", 26),
+        "\tA tab counts as\n\teight columns here"
+    );
+    assert_eq!(
+        wrap("  two spaces and some words", 14),
+        "  two spaces\n  and some\n  words"
+    );
+    // Each line is wrapped on its own
+    assert_eq!(wrap("short\n\tshort too", 24), "short\n\tshort too");
+}
+
+#[test]
+fn wrap_measures_wide_characters() {
+    assert_eq!(wrap("漢字 漢字 漢字", 9), "漢字 漢字\n漢字");
+}
+
+#[test]
+fn wrap_leaves_long_words_whole() {
+    assert_eq!(
+        wrap(
+            "see https://doc.rust-lang.org/rust-by-example/ for more",
+            12
+        ),
+        "see\nhttps://doc.rust-lang.org/rust-by-example/\nfor more"
+    );
+}
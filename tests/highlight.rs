@@ -0,0 +1,88 @@
+// The lexer the ansi format and the exporters highlight listings with
+use rust_by_example::highlight::{tokens, Class};
+
+// The tokens of `code` that aren't plain
+fn marked(code: &str) -> Vec<(Class, &str)> {
+    tokens(code)
+        .into_iter()
+        .filter(|&(class, _)| class != Class::Plain)
+        .collect()
+}
+
+#[test]
+fn keywords_are_whole_words() {
+    assert_eq!(
+        marked("let letter = self.format; fn iffy() {}"),
+        [
+            (Class::Keyword, "let"),
+            (Class::Keyword, "self"),
+            (Class::Keyword, "fn")
+        ]
+    );
+}
+
+#[test]
+fn lifetimes_are_not_chars() {
+    assert_eq!(
+        marked("fn f<'a>(x: &'a str) -> char { 'a' }"),
+        [
+            (Class::Keyword, "fn"),
+            (Class::Lifetime, "'a"),
+            (Class::Lifetime, "'a"),
+            (Class::String, "'a'")
+        ]
+    );
+    assert_eq!(marked("'outer: loop {}")[0], (Class::Lifetime, "'outer"));
+}
+
+#[test]
+fn ranges_are_not_decimal_points() {
+    assert_eq!(
+        marked("0..5; 1.5; 2.0f64; 0..=1_000"),
+        [
+            (Class::Literal, "0"),
+            (Class::Literal, "5"),
+            (Class::Literal, "1.5"),
+            (Class::Literal, "2.0f64"),
+            (Class::Literal, "0"),
+            (Class::Literal, "1_000")
+        ]
+    );
+}
+
+#[test]
+fn not_equals_is_not_a_macro() {
+    assert_eq!(
+        marked("if x != y { println!(\"{}\", x) }"),
+        [
+            (Class::Keyword, "if"),
+            (Class::Macro, "println!"),
+            (Class::String, "\"{}\"")
+        ]
+    );
+}
+
+#[test]
+fn strings_and_comments_keep_what_is_in_them() {
+    assert_eq!(
+        marked(r#"let s = br"fn \"; // let"#),
+        [
+            (Class::Keyword, "let"),
+            (Class::String, r#"br"fn \""#),
+            (Class::Comment, "// let")
+        ]
+    );
+}
+
+#[test]
+fn tokens_are_all_of_the_code() {
+    let code = "use std::fmt;\n\n// A comment with a 'quote\nfn main() {\n\tlet r#type = r#\"raw\"#;\n\tlet c = b'x';\n\tlet é = 1.5e3 + 0x_ff as f64;\n\tassert!(c != b'y', \"{}\", é);\n}\n";
+    let joined: String = tokens(code).into_iter().map(|(_, text)| text).collect();
+    assert_eq!(joined, code);
+    // and an unfinished string doesn't lose anything either
+    let joined: String = tokens("let s = \"no end")
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect();
+    assert_eq!(joined, "let s = \"no end");
+}
@@ -0,0 +1,97 @@
+// Other programs use the chapters through the library, without going through the binary
+use rust_by_example::render::escape_markdown;
+use rust_by_example::{chapters, panics, render_to, run_to_string, Format};
+use std::io;
+
+#[test]
+fn chapters_come_with_their_sections() {
+    let chapters = chapters();
+    assert_eq!(chapters.len(), 12);
+    let custom_types = &chapters[2];
+    assert_eq!(
+        (custom_types.number, custom_types.slug, custom_types.title),
+        (3, "custom-types", "Custom Types")
+    );
+    let section = |slug| {
+        custom_types
+            .sections
+            .iter()
+            .find(|section| section.slug == slug)
+            .unwrap_or_else(|| panic!("custom-types has no {} section", slug))
+    };
+    assert_eq!(section("linked_list").name, "3/linked_list");
+    assert_eq!(section("linked_list").depth, 0);
+    assert_eq!(section("c_structs").depth, 1);
+}
+
+#[test]
+fn sections_run_to_a_string() {
+    let text = run_to_string("8/match_guards", Format::Plain).unwrap().text;
+    assert!(text.contains("\tGuards\n\t======"), "{}", text);
+
+    let json = run_to_string("flow-control/match_guards", Format::Json)
+        .unwrap()
+        .text;
+    assert!(json.lines().all(|line| line.starts_with('{')), "{}", json);
+
+    assert!(run_to_string("8/no_such_section", Format::Plain).is_err());
+}
+
+#[test]
+fn runs_report_their_panics_and_keep_nothing() {
+    // Chapter 9 panics on purpose, which isn't a section panicking
+    let run = run_to_string("9", Format::Plain).unwrap();
+    assert!(run.text.contains("panicked at"), "{}", run.text);
+    assert!(run.panicked.is_empty());
+    let (shown, panicked) = panics::take();
+    assert_eq!((shown, panicked.len()), (0, 0));
+}
+
+#[test]
+fn outlines_are_not_compile_fail_listings() {
+    let json = run_to_string("9/methods", Format::Json).unwrap().text;
+    let outline = json
+        .lines()
+        .find(|line| line.contains("fn area(&self) -> f64 { ... }"))
+        .unwrap_or_else(|| panic!("no outline in {}", json));
+    assert!(
+        outline.contains("\"compile_fail\":false,\"outline\":true"),
+        "{}",
+        outline
+    );
+}
+
+#[test]
+fn markdown_keeps_generics_and_underscores_as_written() {
+    let markdown = run_to_string("9/type_anonymity", Format::Markdown)
+        .unwrap()
+        .text;
+    assert!(markdown.contains(r"parameter \<T\> would"), "{}", markdown);
+
+    assert_eq!(
+        escape_markdown("fn apply<F>(f: F) with `apply<F>` & *a_b*"),
+        r"fn apply\<F\>(f: F) with `apply<F>` \& \*a\_b\*"
+    );
+    assert_eq!(
+        escape_markdown("[1] and a stray ` <b>"),
+        r"\[1\] and a stray \` \<b\>"
+    );
+}
+
+#[test]
+fn runs_only_report_their_own_panics() {
+    // A panic caught earlier on the same thread, e.g. while exporting
+    let leftover = render_to(Format::Plain, Box::new(io::sink()), || {
+        panics::run_section("leftover", || panic!("not this run's"))
+    });
+    assert!(leftover.is_ok());
+    let run = run_to_string("8/match_guards", Format::Plain).unwrap();
+    assert!(
+        run.panicked.is_empty(),
+        "{:?}",
+        run.panicked
+            .iter()
+            .map(|(section, _)| section)
+            .collect::<Vec<_>>()
+    );
+}
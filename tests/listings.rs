@@ -0,0 +1,17 @@
+// Every Rust listing the chapters print has to compile, unless it's marked compile_fail,
+// in which case it mustn't. See `check-listings`.
+use std::process::Command;
+
+#[test]
+fn every_listing_compiles_as_marked() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .arg("check-listings")
+        .output()
+        .expect("couldn't run `check-listings`");
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,27 @@
+// `search` needs something to look for: a blank term is a usage error, not a match for everything
+use std::process::Command;
+
+fn search(terms: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .arg("search")
+        .args(terms)
+        .output()
+        .expect("couldn't run `search`")
+}
+
+#[test]
+fn blank_terms_are_rejected() {
+    for terms in [&[""][..], &["   "], &["", "\t"]] {
+        let output = search(terms);
+        assert_eq!(output.status.code(), Some(2), "search {:?}", terms);
+        assert!(output.stdout.is_empty(), "search {:?}", terms);
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("Usage:"));
+    }
+}
+
+#[test]
+fn terms_are_trimmed() {
+    let output = search(&["  while let "]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("8/while_let"));
+}
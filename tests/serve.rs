@@ -0,0 +1,74 @@
+// Starts `serve` on a free port and asks it for pages the way a browser would.
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+// Stops the server however the test ends
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// Start the server on port 0 and read the address it picked from what it prints
+fn start() -> (Server, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .args(["serve", "0"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("couldn't start `serve`");
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .expect("`serve` didn't say where it is");
+    let address = line
+        .split("http://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or_else(|| panic!("no address in {:?}", line))
+        .to_string();
+    (Server(child), address)
+}
+
+// The status line and body of a GET for `path`
+fn get(address: &str, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(address).expect("couldn't connect to `serve`");
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, address).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").expect("no end to the headers");
+    (head.lines().next().unwrap_or_default().to_string(), body.to_string())
+}
+
+#[test]
+fn serves_chapters_and_sections() {
+    let (_server, address) = start();
+
+    let (status, body) = get(&address, "/");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("03-custom-types.html"), "{}", body);
+
+    let (status, body) = get(&address, "/03-custom-types.html");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("Example 3: Custom Types"), "{}", body);
+
+    // A section on its own is run again for every request
+    let (status, body) = get(&address, "/8/match_guards");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("<base href=\"/\">"), "{}", body);
+    assert_eq!(get(&address, "/8/match_guards").1, body);
+
+    let (status, _) = get(&address, "/style.css");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+
+    let (status, body) = get(&address, "/8/no_such_section");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    assert!(body.contains("no section called `no_such_section`"), "{}", body);
+
+    // Pages are made by running the program with the path, so it can't be taken for an option
+    let (status, _) = get(&address, "--tee");
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+}
@@ -0,0 +1,240 @@
+// Runs every chapter and compares everything it prints with the snapshot checked in under
+// tests/snapshots, so a refactor can't quietly change what a chapter says.
+//
+// After changing a chapter on purpose, re-bless the snapshots with
+//     UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+// and check the diff before committing them.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn binary() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rust-by-example"));
+    // Running chapters records reading progress, which mustn't touch the real config directory
+    command.env("XDG_CONFIG_HOME", env::temp_dir().join("rust-by-example-snapshots"));
+    // and the snapshots are all in the default theme, whatever the person running the tests likes
+    command.env_remove("RUST_BY_EXAMPLE_THEME");
+    command
+}
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("snapshots")
+}
+
+// (number, slug) of every chapter, read from `list`
+fn chapters() -> Vec<(usize, String)> {
+    let output = binary().arg("list").output().expect("couldn't run `list`");
+    String::from_utf8(output.stdout)
+        .expect("`list` printed invalid UTF-8")
+        .lines()
+        .filter_map(|line| {
+            let (number, rest) = line.trim_start().split_once(". ")?;
+            let number = number.parse().ok()?;
+            let slug = rest.rsplit_once(" (")?.1.strip_suffix(')')?;
+            Some((number, slug.to_string()))
+        })
+        .collect()
+}
+
+// Everything a chapter prints: stdout, then stderr under a marker if there is any.
+// They're kept apart because the order they interleave in isn't reliable once piped.
+fn run_chapter(number: usize) -> String {
+    let output = binary()
+        .args(["--format", "plain", "run", &number.to_string()])
+        .output()
+        .expect("couldn't run a chapter");
+    assert!(output.status.success(), "chapter {} failed: {:?}", number, output);
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.stderr.is_empty() {
+        text.push_str("\n----- stderr -----\n");
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+    }
+    text
+}
+
+// `{:p}` addresses change every run, e.g. 0x7ffc9a1b2c3d becomes 0x<address>.
+// Real values in the chapters are short or upper case, so only long lower-case runs count.
+fn normalize_addresses(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find("0x") {
+        normalized.push_str(&rest[..index]);
+        let after = &rest[index + 2..];
+        let digits = after
+            .bytes()
+            .take_while(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(byte))
+            .count();
+        if digits >= 9 {
+            normalized.push_str("0x<address>");
+        } else {
+            normalized.push_str(&rest[index..index + 2 + digits]);
+        }
+        rest = &after[digits..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+// Type names from the standard library depend on where the compiler says they live,
+// e.g. `std::string::String` or `alloc::string::String`, so only the last part is kept
+fn normalize_type_names(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = ["std::", "alloc::", "core::"]
+        .iter()
+        .filter_map(|root| rest.find(root))
+        .min()
+    {
+        let starts_word = rest[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '_' && c != ':');
+        normalized.push_str(&rest[..index]);
+        let path_length = rest[index..]
+            .find(|c: char| !c.is_alphanumeric() && c != '_' && c != ':')
+            .unwrap_or(rest.len() - index);
+        let path = &rest[index..index + path_length];
+        if starts_word {
+            normalized.push_str(path.rsplit("::").next().unwrap_or(path));
+        } else {
+            normalized.push_str(path);
+        }
+        rest = &rest[index + path_length..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+// Caught panics say where they happened, e.g. `panicked at src/a02_primitives.rs:282:27:`,
+// and only the file is kept so editing a chapter doesn't move every line after it
+fn normalize_panic_locations(text: &str) -> String {
+    text.lines()
+        .map(|line| match line.strip_prefix("panicked at ") {
+            Some(location) if location.ends_with(':') => {
+                let file = location.split(':').next().unwrap_or(location);
+                format!("panicked at {}:<line>:\n", file)
+            }
+            _ => format!("{}\n", line),
+        })
+        .collect()
+}
+
+// With --compile-errors, compile-fail cases print rustc's own error, which a new toolchain can word
+// differently, so only the error code is kept, e.g. `error[E0603]: <message>`. The code is what the
+// chapters check.
+fn normalize_compiler_errors(text: &str) -> String {
+    text.lines()
+        .map(|line| match line.strip_prefix("error[") {
+            Some(rest) if rest.contains("]: ") => {
+                let code = rest.split(']').next().unwrap_or(rest);
+                format!("error[{}]: <message>\n", code)
+            }
+            _ => format!("{}\n", line),
+        })
+        .collect()
+}
+
+fn normalize(text: &str) -> String {
+    normalize_compiler_errors(&normalize_panic_locations(&normalize_type_names(
+        &normalize_addresses(text),
+    )))
+}
+
+// The first line that differs, with a little context, for the failure message
+fn first_difference(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let line = (0..expected.len().max(actual.len()))
+        .find(|&index| expected.get(index) != actual.get(index))
+        .unwrap_or(0);
+    format!(
+        "first difference at line {}:\n  expected: {:?}\n  actual:   {:?}",
+        line + 1,
+        expected.get(line).unwrap_or(&"<end of output>"),
+        actual.get(line).unwrap_or(&"<end of output>")
+    )
+}
+
+#[test]
+fn every_chapter_matches_its_snapshot() {
+    let bless = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let chapters = chapters();
+    assert!(!chapters.is_empty(), "`list` didn't show any chapters");
+
+    let mut failures = Vec::new();
+    for (number, slug) in chapters {
+        let path = snapshot_dir().join(format!("{:02}-{}.txt", number, slug));
+        let actual = normalize(&run_chapter(number));
+        if bless {
+            fs::create_dir_all(snapshot_dir()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "chapter {} doesn't match {}, {}",
+                number,
+                path.display(),
+                first_difference(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!(
+                "chapter {} has no snapshot at {}",
+                number,
+                path.display()
+            )),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf these changes are intended, re-bless with `UPDATE_SNAPSHOTS=1 cargo test --test snapshots`",
+        failures.join("\n\n")
+    );
+}
+
+#[test]
+fn addresses_are_normalized() {
+    assert_eq!(
+        normalize_addresses("at 0x7ffc9a1b2c3d, not 0xDEADBEEF or 0xff0000"),
+        "at 0x<address>, not 0xDEADBEEF or 0xff0000"
+    );
+}
+
+#[test]
+fn panic_locations_are_normalized() {
+    assert_eq!(
+        normalize_panic_locations("panicked at src/a02_primitives.rs:282:27:\nindex out of bounds\n"),
+        "panicked at src/a02_primitives.rs:<line>:\nindex out of bounds\n"
+    );
+}
+
+#[test]
+fn compiler_errors_are_normalized() {
+    assert_eq!(
+        normalize_compiler_errors("error[E0382]: use of moved value: `consume`\nerror[E0382]\n"),
+        "error[E0382]: <message>\nerror[E0382]\n"
+    );
+}
+
+#[test]
+fn type_names_are_normalized() {
+    assert_eq!(
+        normalize_type_names("hello: std::string::String, Vec<alloc::vec::Vec<i32>>, mystd::x"),
+        "hello: String, Vec<Vec<i32>>, mystd::x"
+    );
+}
+
+#[test]
+fn compile_errors_come_from_rustc_when_asked() {
+    let output = binary()
+        .args(["--format", "plain", "--compile-errors", "run", "11"])
+        .output()
+        .expect("couldn't run chapter 11");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        text.contains("error[E0603]: function `private_function` is private"),
+        "{}",
+        text
+    );
+}
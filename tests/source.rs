@@ -0,0 +1,70 @@
+// The scanner show_and_run! listings are pulled out of the chapters' source with
+use rust_by_example::source::{dedent, macro_body, skip_literal, unwrap_block};
+
+// Where the literal starting at the front of `code` ends
+fn literal(code: &str) -> &str {
+    &code[..skip_literal(code, 0)]
+}
+
+#[test]
+fn strings_end_at_their_closing_quote() {
+    assert_eq!(literal(r#""a \" b" rest"#), r#""a \" b""#);
+    assert_eq!(literal(r#"r"a \" rest"#), r#"r"a \""#);
+    assert_eq!(literal(r##"r#"a "b" c"# rest"##), r##"r#"a "b" c"#"##);
+    assert_eq!(literal("// comment\nrest"), "// comment\n");
+    assert_eq!(literal("/* a } b */ rest"), "/* a } b */");
+}
+
+#[test]
+fn byte_strings_are_strings_too() {
+    let code = r#"br"\" }"; rest"#;
+    assert_eq!(&code[1..skip_literal(code, 1)], r#"r"\""#);
+    let code = r##"br#"a "b" c"#; rest"##;
+    assert_eq!(&code[1..skip_literal(code, 1)], r##"r#"a "b" c"#"##);
+    // but an r at the end of a word isn't the start of one
+    let code = r#"for"x""#;
+    assert_eq!(skip_literal(code, 2), 3);
+}
+
+#[test]
+fn chars_are_not_lifetimes() {
+    assert_eq!(literal("'a' rest"), "'a'");
+    assert_eq!(literal(r"'\n' rest"), r"'\n'");
+    assert_eq!(literal(r"'\'' rest"), r"'\''");
+    assert_eq!(literal("'é' rest"), "'é'");
+    assert_eq!(literal("'a>(x: &'a str)"), "'");
+    assert_eq!(literal("'outer: loop {}"), "'");
+}
+
+#[test]
+fn macro_bodies_skip_brackets_in_strings_and_chars() {
+    let source = "fn main() {\n    show_and_run! { SOURCE;\n        let s = \"}\";\n        let c = '{';\n        let r = r#\"{{\"#;\n    }\n}\n";
+    assert_eq!(
+        macro_body(source, "show_and_run", 2),
+        Some(" SOURCE;\n        let s = \"}\";\n        let c = '{';\n        let r = r#\"{{\"#;\n    ")
+    );
+    // only calls on or after `line` count
+    assert_eq!(macro_body(source, "show_and_run", 3), None);
+}
+
+#[test]
+fn blocks_are_unwrapped_only_when_they_are_the_whole_thing() {
+    assert_eq!(unwrap_block("  { let x = 1; x }  "), " let x = 1; x ");
+    assert_eq!(unwrap_block("{ 1 } + { 2 }"), "{ 1 } + { 2 }");
+    assert_eq!(unwrap_block("{ \"}\" }"), " \"}\" ");
+}
+
+#[test]
+fn dedent_lines_up_tabs_and_spaces() {
+    assert_eq!(
+        dedent("\n        let x = 1;\n\n        if x > 0 {\n            x;\n        }\n\n"),
+        "let x = 1;\n\nif x > 0 {\n\tx;\n}"
+    );
+    // a tab is four spaces, whichever each line was indented with
+    assert_eq!(
+        dedent("\t\tfn f() {\n            g();\n\t\t}"),
+        "fn f() {\n\tg();\n}"
+    );
+    // and indentation that isn't a whole tab is kept as spaces
+    assert_eq!(dedent("    a(\n          b)"), "a(\n\t  b)");
+}
@@ -0,0 +1,103 @@
+// `--tee` saves what a chapter prints to a file, which shouldn't end up full of color codes
+use rust_by_example::render;
+use rust_by_example::{render_to, set_tee, Format, Notes, Theme};
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
+use std::rc::Rc;
+
+#[test]
+fn tee_writes_the_file_without_color() {
+    let path = env::temp_dir().join(format!("rust-by-example-tee-{}.txt", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .env(
+            "XDG_CONFIG_HOME",
+            env::temp_dir().join("rust-by-example-snapshots"),
+        )
+        .args(["--format", "ansi", "--tee"])
+        .arg(&path)
+        .args(["run", "7"])
+        .output()
+        .expect("couldn't run chapter 7");
+    let saved = fs::read_to_string(&path).expect("--tee didn't write the file");
+    let _ = fs::remove_file(&path);
+    assert!(output.status.success(), "{:?}", output);
+
+    let shown = String::from_utf8_lossy(&output.stdout);
+    assert!(shown.contains('\x1b'), "{}", shown);
+    assert!(!saved.contains('\x1b'), "{}", saved);
+    assert!(saved.contains("Example 7: Expressions"), "{}", saved);
+
+    // and it's the same as asking for plain
+    let plain = Command::new(env!("CARGO_BIN_EXE_rust-by-example"))
+        .env(
+            "XDG_CONFIG_HOME",
+            env::temp_dir().join("rust-by-example-snapshots"),
+        )
+        .args(["--format", "plain", "run", "7"])
+        .output()
+        .expect("couldn't run chapter 7");
+    assert_eq!(saved, String::from_utf8_lossy(&plain.stdout));
+}
+
+// A writer that's already been closed, like stdout piped to `head` once it's seen enough
+struct Closed;
+
+impl Write for Closed {
+    fn write(&mut self, _bytes: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Somewhere to tee to that can be read back afterwards
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Shared {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn tee_keeps_going_when_stdout_stops() {
+    let saved = Shared::default();
+    let tee = saved.clone();
+    let result = render_to(Format::Plain, Box::new(Closed), move || {
+        set_tee(Format::Plain, Notes::Inline, Theme::Ascii, Box::new(tee));
+        for line in 0..100 {
+            render::write_output(&format!("line {}", line));
+        }
+    });
+    assert_eq!(
+        result.map_err(|error| error.kind()),
+        Err(io::ErrorKind::BrokenPipe)
+    );
+    let saved = String::from_utf8(saved.0.take()).unwrap();
+    assert_eq!(saved.lines().count(), 100, "{}", saved);
+    assert_eq!(saved.lines().last(), Some("line 99"));
+}
+
+#[test]
+fn a_tee_that_fails_is_reported_first() {
+    let result = render_to(Format::Plain, Box::new(io::sink()), || {
+        set_tee(Format::Plain, Notes::Inline, Theme::Ascii, Box::new(Closed));
+        render::write_output("lost");
+    });
+    assert!(result.is_err());
+    // and a sink throws everything away without complaint
+    let result = render_to(Format::Json, Box::new(io::sink()), || {
+        render::write_output("gone")
+    });
+    assert!(result.is_ok());
+}